@@ -0,0 +1,234 @@
+/*
+ * Adaptive difficulty: the game reads a player's past games from the score file
+ * and keeps them at a level where they win some and lose some.
+ *
+ * The policy is a simple set of rules, replayed over the history every time:
+ * - PROMOTE_AFTER wins in a row raise the level by one,
+ * - DEMOTE_AFTER losses in a row lower it by one,
+ * - odd levels up double the range, even levels up take a life,
+ * - every level down gives an extra life.
+ *
+ * Because the level is worked out from the history instead of being stored,
+ * `--explain-difficulty` can show exactly how it was reached.
+ * `--reset-difficulty` starts over by remembering how many games to skip.
+ */
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::difficulty::{Difficulty, Settings};
+use crate::i18n::{tr, tr_with};
+use crate::scores::{Score, clean_name};
+
+/// Wins in a row that raise the level.
+pub const PROMOTE_AFTER: u32 = 3;
+/// Losses in a row that lower the level.
+pub const DEMOTE_AFTER: u32 = 2;
+/// The hardest level.
+pub const MAX_LEVEL: i32 = 5;
+/// The easiest level.
+pub const MIN_LEVEL: i32 = -3;
+
+/// A step up or down, and the game that caused it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    /// The game's number in the history, counting from 1.
+    pub game: usize,
+    /// The level after the change.
+    pub level: i32,
+}
+
+/// Where a player's history has taken them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Adaptation {
+    /// 0 plays the chosen difficulty as it is; above is harder, below is easier.
+    pub level: i32,
+    /// How many games were looked at.
+    pub games: usize,
+    pub wins_in_a_row: u32,
+    pub losses_in_a_row: u32,
+    /// Every change of level, oldest first.
+    pub changes: Vec<Change>,
+}
+
+impl Adaptation {
+    /// Replays the rules over `history`, one player's games, oldest first.
+    pub fn learn(history: &[Score]) -> Self {
+        let mut adaptation = Self {
+            games: history.len(),
+            ..Self::default()
+        };
+
+        for (index, score) in history.iter().enumerate() {
+            let step = if score.won {
+                adaptation.losses_in_a_row = 0;
+                adaptation.wins_in_a_row += 1;
+                if adaptation.wins_in_a_row < PROMOTE_AFTER {
+                    continue;
+                }
+                adaptation.wins_in_a_row = 0;
+                1
+            } else {
+                adaptation.wins_in_a_row = 0;
+                adaptation.losses_in_a_row += 1;
+                if adaptation.losses_in_a_row < DEMOTE_AFTER {
+                    continue;
+                }
+                adaptation.losses_in_a_row = 0;
+                -1
+            };
+
+            // clamp keeps the level between the limits; at a limit nothing changes.
+            let level = (adaptation.level + step).clamp(MIN_LEVEL, MAX_LEVEL);
+            if level != adaptation.level {
+                adaptation.level = level;
+                adaptation.changes.push(Change {
+                    game: index + 1,
+                    level,
+                });
+            }
+        }
+
+        adaptation
+    }
+
+    /// `base` made harder or easier for this level. Any change makes it `Custom`.
+    pub fn apply(&self, base: &Settings) -> Settings {
+        let mut settings = base.clone();
+        if self.level == 0 {
+            return settings;
+        }
+
+        settings.difficulty = Difficulty::Custom;
+        if self.level > 0 {
+            let level = self.level.unsigned_abs();
+            // Levels 1, 3, 5 double the range; 2 and 4 take a life.
+            let doublings = level.div_ceil(2);
+            let width = (u64::from(base.max - base.min) + 1) << doublings;
+            settings.max = u32::try_from(u64::from(base.min) + width - 1).unwrap_or(u32::MAX);
+            settings.lives = base.lives.saturating_sub(level / 2).max(1);
+        } else {
+            settings.lives = base.lives + self.level.unsigned_abs();
+        }
+        settings
+    }
+}
+
+impl fmt::Display for Adaptation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: [(&str, &dyn fmt::Display); 4] = [
+            ("promote", &PROMOTE_AFTER),
+            ("demote", &DEMOTE_AFTER),
+            ("min", &MIN_LEVEL),
+            ("max", &MAX_LEVEL),
+        ];
+        writeln!(f, "{}", tr_with("adaptive_rules", &rules))?;
+        writeln!(f, "{}", tr("adaptive_steps"))?;
+        if self.games == 0 {
+            return write!(f, "{}", tr("adaptive_no_games"));
+        }
+
+        let mut previous = 0;
+        for change in &self.changes {
+            let key = if change.level > previous {
+                "adaptive_up"
+            } else {
+                "adaptive_down"
+            };
+            let game = format!("{:>3}", change.game);
+            let values: [(&str, &dyn fmt::Display); 4] = [
+                ("game", &game),
+                ("level", &change.level),
+                ("promote", &PROMOTE_AFTER),
+                ("demote", &DEMOTE_AFTER),
+            ];
+            writeln!(f, "  {}", tr_with(key, &values))?;
+            previous = change.level;
+        }
+
+        let streak = match (self.wins_in_a_row, self.losses_in_a_row) {
+            (0, 0) => tr("adaptive_no_streak"),
+            (wins, 0) => tr_with(
+                "adaptive_win_streak",
+                &[("wins", &wins), ("promote", &PROMOTE_AFTER)],
+            ),
+            (_, losses) => tr_with(
+                "adaptive_loss_streak",
+                &[("losses", &losses), ("demote", &DEMOTE_AFTER)],
+            ),
+        };
+        write!(
+            f,
+            "{}",
+            tr_with(
+                "adaptive_summary",
+                &[
+                    ("games", &self.games),
+                    ("level", &self.level),
+                    ("streak", &streak)
+                ]
+            )
+        )
+    }
+}
+
+/// The games `name` played since their last reset, oldest first.
+pub fn history(scores: &[Score], name: &str, reset: usize) -> Vec<Score> {
+    let name = clean_name(name);
+    scores
+        .iter()
+        .filter(|score| score.name == name)
+        .skip(reset)
+        .cloned()
+        .collect()
+}
+
+/// The file remembering resets, next to the score file and named after it:
+/// `scores.tsv` gets `scores.resets.tsv`, so each score file has resets of its own.
+///
+/// Each line is `name<TAB>games`: how many of the player's games to skip.
+pub fn resets_path(scores_path: &Path) -> PathBuf {
+    let mut name = scores_path.file_stem().unwrap_or_default().to_os_string();
+    name.push(".resets.tsv");
+    scores_path.with_file_name(name)
+}
+
+/// How many of `name`'s games the last reset skips. No reset means 0.
+pub fn load_reset(path: &Path, name: &str) -> io::Result<usize> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error),
+    };
+
+    let name = clean_name(name);
+    let mut reset = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        // The file only grows, so the last line for a player is their latest reset.
+        if let Some((player, games)) = line.split_once('\t')
+            && player == name
+        {
+            reset = games.trim().parse().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: '{games}' is not a number of games", path.display()),
+                )
+            })?;
+        }
+    }
+
+    Ok(reset)
+}
+
+/// Starts `name` over: their first `games` games are ignored from now on.
+pub fn save_reset(path: &Path, name: &str, games: usize) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let name = clean_name(name);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{name}\t{games}")
+}
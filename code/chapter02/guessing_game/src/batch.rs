@@ -0,0 +1,88 @@
+/*
+ * Batch mode, for scripts instead of people: the guesses come from a list
+ * (`--guesses 50,25,37`) or from stdin (`--batch`), and every answer is
+ * one JSON object on its own line ("JSON Lines"), easy to read from any language:
+ *
+ *     {"guess": 50, "result": "too big", "lives_left": 4}
+ *     {"input": "abc", "error": "'abc' is not a number. ...", "lives_left": 4}
+ *     {"state": "won", "secret": 37, "guesses": 3}
+ *
+ * The exit code tells the script how it went without reading anything: see `exit_code`.
+ */
+use std::io::{self, Write};
+
+use crate::game::{Game, State};
+use crate::transcript::{json_string, result_name};
+
+/// The exit code for a won game.
+pub const EXIT_WON: i32 = 0;
+/// The exit code for a lost game. 1 and 2 already mean errors and bad arguments.
+pub const EXIT_LOST: i32 = 3;
+
+/// Splits a list like "50, 25,37" into guesses. Empty items are skipped.
+pub fn parse_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Plays `game` with `guesses`, writing one JSON line per guess and one for the result.
+///
+/// Guesses after the game ended are ignored. If the guesses run out first, the game is lost.
+pub fn run<I, W>(game: &mut Game, guesses: I, output: &mut W) -> io::Result<State>
+where
+    I: IntoIterator<Item = String>,
+    W: Write,
+{
+    for text in guesses {
+        if game.is_over() {
+            break;
+        }
+
+        let answer = game
+            .parse_guess(&text)
+            .and_then(|guess| game.guess(guess).map(|ordering| (guess, ordering)));
+
+        match answer {
+            Ok((guess, ordering)) => writeln!(
+                output,
+                "{{\"guess\": {guess}, \"result\": \"{}\", \"lives_left\": {}}}",
+                result_name(ordering),
+                game.lives()
+            )?,
+            Err(error) => writeln!(
+                output,
+                "{{\"input\": {}, \"error\": {}, \"lives_left\": {}}}",
+                json_string(&text),
+                json_string(&error.to_string()),
+                game.lives()
+            )?,
+        }
+    }
+
+    game.forfeit();
+    let state = if game.state() == State::Won {
+        "won"
+    } else {
+        "lost"
+    };
+    writeln!(
+        output,
+        "{{\"state\": \"{state}\", \"secret\": {}, \"guesses\": {}}}",
+        game.secret(),
+        game.guesses().len()
+    )?;
+    output.flush()?;
+
+    Ok(game.state())
+}
+
+/// `EXIT_WON` or `EXIT_LOST`.
+pub fn exit_code(state: State) -> i32 {
+    match state {
+        State::Won => EXIT_WON,
+        State::Lost | State::Abandoned | State::Playing => EXIT_LOST,
+    }
+}
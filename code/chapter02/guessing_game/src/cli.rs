@@ -0,0 +1,415 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::batch;
+use crate::codebreaker::Rules;
+use crate::config::{self, Config};
+use crate::difficulty::{Difficulty, Settings};
+use crate::i18n::Language;
+use crate::net;
+use crate::number::{Number, NumberKind, ParseError};
+use crate::scores;
+use crate::server;
+use crate::transcript;
+
+pub const USAGE: &str = "\
+Usage: guessing_game [COMMAND] [OPTIONS]
+
+Commands:
+  play                      guess the computer's number (the default)
+  host [ADDRESS]            pick a number for a player who joins over TCP (default 127.0.0.1:7878)
+  join [ADDRESS]            guess the number of a player who is hosting
+  serve [ADDRESS]           play in the browser at http://ADDRESS/ (default 127.0.0.1:8080)
+  reverse                   think of a number and let the computer guess it
+  codebreaker               crack a secret code of digits (Mastermind), uses -d, --lives and the time options
+
+Options:
+  -d, --difficulty <LEVEL>  easy, normal, hard or custom
+      --min <N>             smallest possible secret number
+      --max <N>             largest possible secret number
+      --lives <N>           number of wrong guesses allowed
+      --numbers <KIND>      whole (the default), signed (like --min -50 --max 50) or decimal
+      --tolerance <T>       how close a guess must be to win (default 0.5 for decimal, else 0)
+      --time-limit <SECS>   seconds allowed for the whole game
+      --turn-time <SECS>    seconds allowed for each guess, too slow costs a life
+      --speed-run           lightning round: 10 seconds per guess, 60 for the game
+      --hints, --no-hints   say how close each guess is (warmer/colder), or don't
+      --adaptive, --no-adaptive
+                            adjust the range and lives to how your past games went, or don't
+      --explain-difficulty  show how your past games set the adaptive level, instead of playing
+      --reset-difficulty    start the adaptive level over from 0, instead of playing
+      --color, --no-color   colour the output (default: only in a terminal, and not if NO_COLOR is set)
+      --tui                 full-screen view with the range, guess history and hearts
+      --rounds <N>          play a tournament of N rounds and show a scoreboard at the end
+      --seed <N>            replay the same secret numbers (also GUESSING_GAME_SEED)
+      --auto                let the computer play with a binary search
+      --benchmark <GAMES>   let the computer play many games and show the guesses it needed
+      --guesses <LIST>      play these guesses, like 50,25,37, and print JSON lines
+      --batch               read the guesses from stdin instead and print JSON lines
+      --transcript <FILE>   save every guess to a .json or .csv file
+      --name <NAME>         player name saved with your score
+      --scores              print the leaderboard instead of playing
+      --scores-file <PATH>  where scores are saved (default: your data folder)
+      --lang <LANGUAGE>     en, es or de (default: from LC_ALL, LC_MESSAGES or LANG, else en)
+      --config <PATH>       read defaults from this TOML file (default: ~/.config/guessing_game.toml)
+  -h, --help                print this help
+
+Without any of these options, a menu asks for the difficulty at startup
+(--auto, --benchmark, --guesses and --batch play the normal difficulty instead).
+--guesses and --batch exit with 0 for a win and 3 for a loss.
+Signed and decimal numbers are for the classic game: they don't work with other commands,
+--tui, --auto, --benchmark, --guesses, --batch or --transcript, and their scores aren't saved.
+Without --rounds, you are asked after every game whether to play again.
+The adaptive level comes from your scores since the last reset (see --explain-difficulty).
+The config file can set difficulty, min, max, lives, hints, adaptive, name, color and lang;
+options given here override it, and -d also sets aside its min, max and lives.";
+
+/// The clocks `--speed-run` starts, unless `--turn-time`/`--time-limit` say otherwise.
+pub const SPEED_RUN_TURN: Duration = Duration::from_secs(10);
+pub const SPEED_RUN_GAME: Duration = Duration::from_secs(60);
+
+/// Environment variable read when `--seed` is not given.
+pub const SEED_ENV: &str = "GUESSING_GAME_SEED";
+
+/// Which game to run.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    /// Listen on this address for a guest to join.
+    Host(String),
+    /// Connect to a host at this address.
+    Join(String),
+    /// Serve the game over HTTP on this address.
+    Serve(String),
+    /// The computer guesses the player's number.
+    Reverse,
+    /// Crack a code of digits instead of guessing a number.
+    Codebreaker,
+}
+
+/// The command-line flags, before they are turned into game `Settings`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub command: Command,
+    pub difficulty: Option<Difficulty>,
+    /// Kept as text until we know which kind of number to read it as.
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub lives: Option<u32>,
+    pub numbers: NumberKind,
+    pub tolerance: Option<String>,
+    pub time_limit: Option<u64>,
+    pub turn_time: Option<u64>,
+    pub speed_run: bool,
+    pub hints: Option<bool>,
+    pub adaptive: Option<bool>,
+    pub explain_difficulty: bool,
+    pub reset_difficulty: bool,
+    pub color: Option<bool>,
+    pub tui: bool,
+    pub rounds: Option<u32>,
+    pub seed: Option<u64>,
+    pub auto: bool,
+    pub benchmark: Option<u32>,
+    pub guesses: Option<Vec<String>>,
+    pub batch: bool,
+    pub transcript: Option<PathBuf>,
+    pub name: Option<String>,
+    pub show_scores: bool,
+    pub scores_file: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    pub lang: Option<Language>,
+    pub help: bool,
+}
+
+/*
+ * We take anything that can be turned into an iterator of Strings,
+ * so main can pass std::env::args() and tests can pass a plain Vec.
+ */
+pub fn parse<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options::default();
+    let mut words = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // Accept both `--lives 3` and `--lives=3`.
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg, None),
+        };
+
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or(format!("{flag} needs a value"))
+        };
+
+        match flag.as_str() {
+            "-d" | "--difficulty" => options.difficulty = Some(value()?.parse()?),
+            "--min" => options.min = Some(value()?),
+            "--max" => options.max = Some(value()?),
+            "--lives" => options.lives = Some(parse_number(&flag, &value()?)?),
+            "--numbers" => options.numbers = value()?.parse()?,
+            "--tolerance" => options.tolerance = Some(value()?),
+            "--time-limit" => options.time_limit = Some(parse_number(&flag, &value()?)?),
+            "--turn-time" => options.turn_time = Some(parse_number(&flag, &value()?)?),
+            "--speed-run" => options.speed_run = true,
+            "--hints" => options.hints = Some(true),
+            "--no-hints" => options.hints = Some(false),
+            "--adaptive" => options.adaptive = Some(true),
+            "--no-adaptive" => options.adaptive = Some(false),
+            "--explain-difficulty" => options.explain_difficulty = true,
+            "--reset-difficulty" => options.reset_difficulty = true,
+            "--color" => options.color = Some(true),
+            "--no-color" => options.color = Some(false),
+            "--tui" => options.tui = true,
+            "--rounds" => match parse_number(&flag, &value()?)? {
+                0 => return Err(String::from("--rounds needs at least 1 round")),
+                rounds => options.rounds = Some(rounds),
+            },
+            "--seed" => options.seed = Some(parse_number(&flag, &value()?)?),
+            "--auto" => options.auto = true,
+            "--benchmark" => options.benchmark = Some(parse_number(&flag, &value()?)?),
+            "--guesses" => options.guesses = Some(batch::parse_list(&value()?)),
+            "--batch" => options.batch = true,
+            "--transcript" => {
+                let path = PathBuf::from(value()?);
+                // Check the extension now, rather than after the game is played.
+                transcript::Format::from_path(&path)?;
+                options.transcript = Some(path);
+            }
+            "--name" => options.name = Some(value()?),
+            "--scores" => options.show_scores = true,
+            "--scores-file" => options.scores_file = Some(PathBuf::from(value()?)),
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--lang" => options.lang = Some(value()?.parse()?),
+            "-h" | "--help" => options.help = true,
+            other if other.starts_with('-') => {
+                return Err(format!("unknown argument '{other}'"));
+            }
+            // Anything that isn't a flag is part of the command, like `host 0.0.0.0:7878`.
+            _ => words.push(flag),
+        }
+    }
+
+    options.command = parse_command(&words)?;
+
+    // The browser game only checks the game clock, when the page asks for the game.
+    if matches!(options.command, Command::Serve(_))
+        && (options.turn_time.is_some() || options.speed_run)
+    {
+        return Err(String::from(
+            "serve can't time each guess, use --time-limit for the whole game instead",
+        ));
+    }
+
+    let whole_only = options.command != Command::Play
+        || options.tui
+        || options.auto
+        || options.benchmark.is_some()
+        || options.is_batch()
+        || options.transcript.is_some();
+    if options.numbers != NumberKind::Whole && whole_only {
+        return Err(format!(
+            "--numbers {} only works for the classic game, see the note below",
+            options.numbers
+        ));
+    }
+
+    Ok(options)
+}
+
+fn parse_command(words: &[String]) -> Result<Command, String> {
+    let Some((name, rest)) = words.split_first() else {
+        return Ok(Command::Play);
+    };
+
+    let address = |default: &str| match rest {
+        [] => Ok(default.to_string()),
+        [address] => Ok(address.clone()),
+        [_, extra, ..] => Err(format!("unexpected argument '{extra}'")),
+    };
+
+    match name.as_str() {
+        "play" if rest.is_empty() => Ok(Command::Play),
+        "reverse" if rest.is_empty() => Ok(Command::Reverse),
+        "codebreaker" | "mastermind" if rest.is_empty() => Ok(Command::Codebreaker),
+        "host" => Ok(Command::Host(address(net::DEFAULT_ADDRESS)?)),
+        "join" => Ok(Command::Join(address(net::DEFAULT_ADDRESS)?)),
+        "serve" => Ok(Command::Serve(address(server::DEFAULT_ADDRESS)?)),
+        "play" | "reverse" | "codebreaker" | "mastermind" => {
+            Err(format!("unexpected argument '{}'", rest[0]))
+        }
+        other => Err(format!("unknown command '{other}'")),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{flag} expects a whole number, got '{value}'"))
+}
+
+/// Reads `--min`, `--max` or `--tolerance` as whichever kind of number is being played.
+fn parse_value<N: Number>(flag: &str, value: &str) -> Result<N, String> {
+    N::parse(value.trim()).map_err(|error| match error {
+        ParseError::NotANumber => format!("{flag} expects a number, got '{value}'"),
+        ParseError::Negative => {
+            format!("{flag} can't be negative, use --numbers signed for negative numbers")
+        }
+        ParseError::TooBig => format!("{flag} is too big, got '{value}'"),
+    })
+}
+
+impl Options {
+    /// True when a script, not a person, provides the guesses.
+    pub fn is_batch(&self) -> bool {
+        self.guesses.is_some() || self.batch
+    }
+
+    /// Fills in everything the command line left out from `config`.
+    ///
+    /// The config's range and lives are skipped when `--difficulty` was given:
+    /// `-d hard` should play hard, not a custom game with the config's numbers.
+    pub fn apply_config(&mut self, config: Config) -> Result<(), String> {
+        let difficulty_given = self.difficulty.is_some();
+        if !difficulty_given && let Some(difficulty) = config.difficulty {
+            self.difficulty = Some(difficulty.parse()?);
+        }
+        // Option::or keeps our value if we have one, and takes the config's otherwise.
+        if !difficulty_given {
+            self.min = self.min.take().or(config.min.map(|min| min.to_string()));
+            self.max = self.max.take().or(config.max.map(|max| max.to_string()));
+            self.lives = self.lives.or(config.lives);
+        }
+        self.hints = self.hints.or(config.hints);
+        self.adaptive = self.adaptive.or(config.adaptive);
+        self.name = self.name.take().or(config.name);
+        self.color = self.color.or(config.color);
+        if self.lang.is_none()
+            && let Some(lang) = config.lang
+        {
+            self.lang = Some(lang.parse()?);
+        }
+        Ok(())
+    }
+
+    /// The config file from `--config`, falling back to the per-user config folder.
+    pub fn config_path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(config::default_path)
+    }
+
+    /// True when any of the range/lives/time flags were given.
+    fn has_overrides(&self) -> bool {
+        self.min.is_some()
+            || self.max.is_some()
+            || self.tolerance.is_some()
+            || self.lives.is_some()
+            || self.time_limit.is_some()
+            || self.turn_time.is_some()
+            || self.speed_run
+    }
+
+    /// The settings chosen on the command line, or `None` when the menu should ask.
+    /// `--min`, `--max` and `--tolerance` are read as `N`, the kind of number being played.
+    ///
+    /// Individual flags override the preset values; using any of them makes the game `Custom`.
+    pub fn settings_for<N: Number>(&self) -> Result<Option<Settings<N>>, String> {
+        if self.difficulty.is_none() && !self.has_overrides() {
+            return Ok(None);
+        }
+
+        let mut settings = self
+            .difficulty
+            .unwrap_or(Difficulty::Custom)
+            .settings()
+            .to::<N>();
+        if self.has_overrides() {
+            settings.difficulty = Difficulty::Custom;
+        }
+        if let Some(min) = &self.min {
+            settings.min = parse_value("--min", min)?;
+        }
+        if let Some(max) = &self.max {
+            settings.max = parse_value("--max", max)?;
+        }
+        if let Some(tolerance) = &self.tolerance {
+            settings.tolerance = parse_value("--tolerance", tolerance)?;
+        }
+        if let Some(lives) = self.lives {
+            settings.lives = lives;
+        }
+        if self.speed_run {
+            settings.turn_limit = Some(SPEED_RUN_TURN);
+            settings.time_limit = Some(SPEED_RUN_GAME);
+        }
+        if let Some(secs) = self.time_limit {
+            settings.time_limit = Some(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.turn_time {
+            settings.turn_limit = Some(Duration::from_secs(secs));
+        }
+
+        settings.validate()?;
+        Ok(Some(settings))
+    }
+
+    /// The code-breaking rules: the `--difficulty` preset (normal by default) with
+    /// `--lives`, `--time-limit`, `--turn-time` and `--speed-run` applied on top.
+    pub fn codebreaker_rules(&self) -> Result<Rules, String> {
+        let mut rules = Rules::for_difficulty(self.difficulty.unwrap_or(Difficulty::Normal));
+        if let Some(lives) = self.lives {
+            rules.lives = lives;
+        }
+        if self.speed_run {
+            rules.turn_limit = Some(SPEED_RUN_TURN);
+            rules.time_limit = Some(SPEED_RUN_GAME);
+        }
+        if let Some(secs) = self.time_limit {
+            rules.time_limit = Some(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.turn_time {
+            rules.turn_limit = Some(Duration::from_secs(secs));
+        }
+
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// The seed from `--seed`, falling back to the `GUESSING_GAME_SEED` variable.
+    ///
+    /// `None` means neither was set and the caller should pick a fresh seed.
+    pub fn seed(&self) -> Result<Option<u64>, String> {
+        if self.seed.is_some() {
+            return Ok(self.seed);
+        }
+
+        match std::env::var(SEED_ENV) {
+            Ok(value) => parse_number(SEED_ENV, &value).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// The player name from `--name`, falling back to the login name.
+    pub fn player_name(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| String::from("player"))
+    }
+
+    /// The score file from `--scores-file`, falling back to the per-user data folder.
+    pub fn scores_path(&self) -> Option<PathBuf> {
+        self.scores_file.clone().or_else(scores::default_path)
+    }
+}
@@ -0,0 +1,316 @@
+/*
+ * Code breaking, a harder follow-up to guessing a number (also known as Mastermind).
+ *
+ * The secret is a row of digits, like 3 1 4 1. After every guess you learn
+ * - how many digits are exactly right: the right digit in the right place
+ * - how many are misplaced: a digit the code has, but somewhere else
+ * but not which ones. A wrong guess costs a life, just like in the number game,
+ * and the same play loop runs both games (see the Puzzle trait in play.rs).
+ */
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::difficulty::Difficulty;
+use crate::game::{GuessError, State, trimmed};
+use crate::i18n::tr_with;
+use crate::play::{Puzzle, not_counted};
+
+/// How long the code is, which digits it uses and how many lives the player has.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// How many digits the code has.
+    pub length: usize,
+    /// The code only uses digits from 1 up to this one.
+    pub highest: u8,
+    pub lives: u32,
+    /// How long the whole game may take, if there is a limit.
+    pub time_limit: Option<Duration>,
+    /// How long each guess may take, if there is a limit.
+    pub turn_limit: Option<Duration>,
+}
+
+impl Rules {
+    /// The preset for each difficulty. Custom starts from the normal preset.
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        let (length, highest, lives) = match difficulty {
+            Difficulty::Easy => (3, 6, 10),
+            Difficulty::Normal | Difficulty::Custom => (4, 6, 10),
+            Difficulty::Hard => (5, 9, 12),
+        };
+        Self {
+            length,
+            highest,
+            lives,
+            time_limit: None,
+            turn_limit: None,
+        }
+    }
+
+    /// Checks that the rules make a playable game.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.length == 0 {
+            return Err(String::from("the code needs at least 1 digit"));
+        }
+        if !(1..=9).contains(&self.highest) {
+            return Err(String::from("the highest digit must be between 1 and 9"));
+        }
+        if self.lives == 0 {
+            return Err(String::from("you need at least 1 life"));
+        }
+        if self.time_limit == Some(Duration::ZERO) || self.turn_limit == Some(Duration::ZERO) {
+            return Err(String::from("time limits must be at least 1 second"));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&tr_with(
+            "codebreaker_rules",
+            &[
+                ("length", &self.length),
+                ("highest", &self.highest),
+                ("lives", &self.lives),
+            ],
+        ))?;
+        // The same words as the number game's settings use for its clocks.
+        if let Some(limit) = self.time_limit {
+            let seconds = limit.as_secs();
+            f.write_str(&tr_with("settings_time_limit", &[("seconds", &seconds)]))?;
+        }
+        if let Some(limit) = self.turn_limit {
+            let seconds = limit.as_secs();
+            f.write_str(&tr_with("settings_turn_limit", &[("seconds", &seconds)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// The answer to one guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feedback {
+    /// Right digit in the right place.
+    pub exact: usize,
+    /// Right digit in the wrong place.
+    pub misplaced: usize,
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&tr_with(
+            "codebreaker_feedback",
+            &[("exact", &self.exact), ("misplaced", &self.misplaced)],
+        ))
+    }
+}
+
+/// Compares `guess` with `secret`. Both must have the same length.
+pub fn score(secret: &[u8], guess: &[u8]) -> Feedback {
+    let exact = secret
+        .iter()
+        .zip(guess)
+        .filter(|(secret, guess)| secret == guess)
+        .count();
+
+    /*
+     * Every digit the two have in common counts once for each time it appears in both,
+     * so a guess of 1 1 1 1 against 1 2 3 4 only matches one 1.
+     * Taking away the exact ones leaves the misplaced ones.
+     */
+    let common: usize = (0..=9)
+        .map(|digit| {
+            let in_secret = secret.iter().filter(|&&d| d == digit).count();
+            let in_guess = guess.iter().filter(|&&d| d == digit).count();
+            in_secret.min(in_guess)
+        })
+        .sum();
+
+    Feedback {
+        exact,
+        misplaced: common - exact,
+    }
+}
+
+/// Writes a code as its digits, like "3141".
+pub fn code_to_string(code: &[u8]) -> String {
+    code.iter().map(|digit| digit.to_string()).collect()
+}
+
+/// Why a code was not accepted: the same errors as a number, with codes written out as text.
+pub type CodeError = GuessError<String>;
+
+/// A code-breaking game: the same kind of state machine as `Game`, with a code as the secret.
+#[derive(Debug, Clone)]
+pub struct Codebreaker {
+    rules: Rules,
+    secret: Vec<u8>,
+    lives: u32,
+    guesses: Vec<Vec<u8>>,
+    state: State,
+}
+
+impl Codebreaker {
+    /// Starts a game with a known secret code.
+    pub fn new(rules: Rules, secret: Vec<u8>) -> Self {
+        Self {
+            lives: rules.lives,
+            rules,
+            secret,
+            guesses: Vec::new(),
+            state: State::Playing,
+        }
+    }
+
+    /// Starts a game with a code picked by `rng`. Digits may repeat.
+    pub fn random<R: Rng + ?Sized>(rules: Rules, rng: &mut R) -> Self {
+        let secret = (0..rules.length)
+            .map(|_| rng.random_range(1..=rules.highest))
+            .collect();
+        Self::new(rules, secret)
+    }
+
+    /// Turns typed text into a code. Spaces and commas between digits are allowed: "1 2 3 4".
+    pub fn parse_guess(&self, input: &str) -> Result<Vec<u8>, CodeError> {
+        let input = trimmed(input)?;
+
+        let code = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            // to_digit gives the value of '0'..='9' and None for anything else.
+            .map(|c| c.to_digit(10).map(|digit| digit as u8))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| CodeError::NotANumber(input.to_string()))?;
+
+        self.check(&code)?;
+        Ok(code)
+    }
+
+    /// Rejects codes of the wrong shape or already tried.
+    fn check(&self, code: &[u8]) -> Result<(), CodeError> {
+        if self.state != State::Playing {
+            return Err(CodeError::GameOver);
+        }
+        if code.len() != self.rules.length {
+            return Err(CodeError::WrongLength {
+                expected: self.rules.length,
+                got: code.len(),
+            });
+        }
+        if let Some(&digit) = code
+            .iter()
+            .find(|&&digit| digit < 1 || digit > self.rules.highest)
+        {
+            return Err(CodeError::DigitOutOfRange {
+                digit,
+                highest: self.rules.highest,
+            });
+        }
+        if self.guesses.iter().any(|guess| guess == code) {
+            return Err(CodeError::Repeated(code_to_string(code)));
+        }
+        Ok(())
+    }
+
+    /// Compares `code` with the secret. A wrong guess costs a life.
+    pub fn guess(&mut self, code: &[u8]) -> Result<Feedback, CodeError> {
+        self.check(code)?;
+
+        self.guesses.push(code.to_vec());
+        let feedback = score(&self.secret, code);
+        if feedback.exact == self.rules.length {
+            self.state = State::Won;
+        } else {
+            self.lives -= 1;
+            if self.lives == 0 {
+                self.state = State::Lost;
+            }
+        }
+
+        Ok(feedback)
+    }
+
+    /// The time for a guess ran out: this costs a life, just like a wrong guess.
+    pub fn miss(&mut self) {
+        if self.state != State::Playing {
+            return;
+        }
+
+        self.lives -= 1;
+        if self.lives == 0 {
+            self.state = State::Lost;
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// Every accepted guess, oldest first.
+    pub fn guesses(&self) -> &[Vec<u8>] {
+        &self.guesses
+    }
+}
+
+impl Puzzle for Codebreaker {
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    fn time_limit(&self) -> Option<Duration> {
+        self.rules.time_limit
+    }
+
+    fn turn_limit(&self) -> Option<Duration> {
+        self.rules.turn_limit
+    }
+
+    fn miss(&mut self) {
+        Codebreaker::miss(self)
+    }
+
+    fn forfeit(&mut self) {
+        if self.state == State::Playing {
+            self.state = State::Lost;
+        }
+    }
+
+    fn abandon(&mut self) {
+        if self.state == State::Playing {
+            self.state = State::Abandoned;
+        }
+    }
+
+    fn reveal(&self) -> String {
+        tr_with(
+            "secret_code_was",
+            &[("secret", &code_to_string(&self.secret))],
+        )
+    }
+
+    fn answer<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<()> {
+        let code = match self.parse_guess(line) {
+            Ok(code) => code,
+            Err(error) => return writeln!(output, "{}", not_counted(&error)),
+        };
+
+        let guess = code_to_string(&code);
+        writeln!(output, "{}", tr_with("you_guessed", &[("guess", &guess)]))?;
+
+        match self.guess(&code) {
+            Ok(feedback) => writeln!(output, "{feedback}."),
+            Err(error) => Err(io::Error::other(error)),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * Coloured output with ANSI escape codes, the same trick the TUI uses:
+ * \x1b[32m switches the text to green and \x1b[0m switches back.
+ *
+ * Whether to use colour is decided once for the whole program (a terminal
+ * shows the colours, a file would just get the escape codes), so it is kept
+ * in a static. AtomicBool is a bool that can be shared safely between threads.
+ */
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Cyan => "\x1b[36m",
+        }
+    }
+}
+
+/// Turns coloured output on or off. It starts off.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// `text` in `color`, or unchanged when colour is off.
+pub fn paint(text: &str, color: Color) -> String {
+    if enabled() {
+        format!("{}{text}\x1b[0m", color.code())
+    } else {
+        text.to_string()
+    }
+}
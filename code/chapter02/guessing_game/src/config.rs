@@ -0,0 +1,76 @@
+/*
+ * Defaults from a TOML file, so a shared computer can be set up once:
+ *
+ *     # ~/.config/guessing_game.toml
+ *     min = 1
+ *     max = 20
+ *     lives = 6
+ *     hints = true
+ *     adaptive = true
+ *     name = "Room 4"
+ *     color = true
+ *     lang = "es"
+ *
+ * serde's derive writes the code that turns the parsed TOML into our struct.
+ * Every field is an Option, because every setting may be left out;
+ * flags on the command line still win over anything in here.
+ */
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// The settings a config file may contain.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+// A misspelled key is reported instead of being silently ignored.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub difficulty: Option<String>,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    pub lives: Option<u32>,
+    pub hints: Option<bool>,
+    pub adaptive: Option<bool>,
+    pub name: Option<String>,
+    pub color: Option<bool>,
+    pub lang: Option<String>,
+}
+
+/// Where the config file is looked for when `--config` is not given.
+///
+/// `$XDG_CONFIG_HOME` or `~/.config` on Linux and macOS, `%APPDATA%` on Windows.
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    config_dir.map(|dir| dir.join("guessing_game.toml"))
+}
+
+/// Reads a config from TOML text.
+pub fn parse(text: &str) -> Result<Config, String> {
+    toml::from_str(text).map_err(|error| error.to_string())
+}
+
+/// Reads the config file at `path`.
+///
+/// A missing file is only fine when `required` is false: the default file is optional,
+/// but a file named with `--config` has to exist.
+pub fn load(path: &Path, required: bool) -> Result<Config, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound && !required => {
+            return Ok(Config::default());
+        }
+        Err(error) => return Err(format!("can't read {}: {error}", path.display())),
+    };
+
+    parse(&text).map_err(|message| format!("in {}: {message}", path.display()))
+}
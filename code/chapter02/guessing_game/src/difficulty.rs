@@ -0,0 +1,177 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::i18n::{tr, tr_with};
+use crate::number::Number;
+
+/*
+ * An enum lists every value a type can have.
+ * The derive attribute asks the compiler to write common trait impls for us:
+ * - Debug: print with {:?}
+ * - Clone, Copy: duplicate the value instead of moving it
+ * - PartialEq, Eq: compare with ==
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Custom,
+}
+
+/// Everything that shapes one game: the secret's range, the lives, an optional clock and hints.
+///
+/// `N` is the kind of number the secret is (see number.rs).
+/// `= u32` makes it optional: a plain `Settings` is `Settings<u32>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings<N = u32> {
+    pub difficulty: Difficulty,
+    pub min: N,
+    pub max: N,
+    /// How far from the secret a guess may be and still win. Only useful for decimals.
+    pub tolerance: N,
+    pub lives: u32,
+    pub time_limit: Option<Duration>,
+    /// Seconds allowed for each guess; waiting longer costs a life.
+    pub turn_limit: Option<Duration>,
+    /// Say how close each guess is, not just whether it is too small or too big.
+    pub hints: bool,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Custom,
+    ];
+
+    /// The preset values for this level. `Custom` starts from the `Normal` values.
+    pub fn settings(self) -> Settings {
+        let (min, max, lives, time_limit) = match self {
+            Difficulty::Easy => (1, 50, 10, None),
+            Difficulty::Normal | Difficulty::Custom => (1, 100, 5, None),
+            Difficulty::Hard => (1, 1000, 7, Some(Duration::from_secs(60))),
+        };
+
+        Settings {
+            difficulty: self,
+            min,
+            max,
+            lives,
+            time_limit,
+            turn_limit: None,
+            hints: false,
+            tolerance: 0,
+        }
+    }
+}
+
+/*
+ * Implementing Display lets us use a Difficulty directly inside println!("{}").
+ */
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom => "custom",
+        };
+        f.write_str(name)
+    }
+}
+
+/*
+ * Implementing FromStr lets us write "hard".parse::<Difficulty>(),
+ * the same way we parse the guess into a u32.
+ */
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" | "1" => Ok(Difficulty::Easy),
+            "normal" | "2" => Ok(Difficulty::Normal),
+            "hard" | "3" => Ok(Difficulty::Hard),
+            "custom" | "4" => Ok(Difficulty::Custom),
+            other => Err(format!(
+                "unknown difficulty '{other}' (expected easy, normal, hard or custom)"
+            )),
+        }
+    }
+}
+
+impl Settings {
+    /// The same settings for another kind of number, with its default tolerance.
+    pub fn to<N: Number>(&self) -> Settings<N> {
+        Settings {
+            difficulty: self.difficulty,
+            min: N::from(self.min),
+            max: N::from(self.max),
+            tolerance: N::DEFAULT_TOLERANCE,
+            lives: self.lives,
+            time_limit: self.time_limit,
+            turn_limit: self.turn_limit,
+            hints: self.hints,
+        }
+    }
+}
+
+impl<N: Number> Settings<N> {
+    /// True when a clock is running, so waiting for a guess has to be able to time out.
+    pub fn is_timed(&self) -> bool {
+        self.time_limit.is_some() || self.turn_limit.is_some()
+    }
+
+    /// Checks the values make a playable game.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min > self.max {
+            return Err(format!(
+                "the range {}..={} is empty (min must not be greater than max)",
+                self.min, self.max
+            ));
+        }
+        if self.tolerance < N::from(0) {
+            return Err(String::from("the tolerance must not be negative"));
+        }
+        if self.lives == 0 {
+            return Err(String::from("you need at least 1 life to play"));
+        }
+        if self.time_limit == Some(Duration::ZERO) || self.turn_limit == Some(Duration::ZERO) {
+            return Err(String::from("time limits must be at least 1 second"));
+        }
+        Ok(())
+    }
+}
+
+impl<N: Number> fmt::Display for Settings<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&tr_with(
+            "settings",
+            &[
+                ("difficulty", &self.difficulty),
+                ("min", &self.min),
+                ("max", &self.max),
+                ("lives", &self.lives),
+            ],
+        ))?;
+        if let Some(limit) = self.time_limit {
+            let seconds = limit.as_secs();
+            f.write_str(&tr_with("settings_time_limit", &[("seconds", &seconds)]))?;
+        }
+        if let Some(limit) = self.turn_limit {
+            let seconds = limit.as_secs();
+            f.write_str(&tr_with("settings_turn_limit", &[("seconds", &seconds)]))?;
+        }
+        if self.tolerance > N::from(0) {
+            let tolerance = &self.tolerance;
+            f.write_str(&tr_with("settings_tolerance", &[("tolerance", tolerance)]))?;
+        }
+        if self.hints {
+            f.write_str(&tr("settings_hints"))?;
+        }
+        Ok(())
+    }
+}
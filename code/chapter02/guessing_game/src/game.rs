@@ -0,0 +1,308 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::difficulty::Settings;
+use crate::i18n::{tr, tr_with};
+use crate::number::{Number, NumberKind, ParseError};
+
+/// Where a game stands after the latest guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Playing,
+    Won,
+    Lost,
+    /// The player left before the end, e.g. the input ran out: neither a win nor a loss.
+    Abandoned,
+}
+
+/// Why a guess was not accepted. None of these cost a life.
+///
+/// `N` is what a guess is: a number, or for the code-breaking game a code written out as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError<N = u32> {
+    /// Nothing was typed.
+    Empty,
+    /// The text is not a whole number.
+    NotANumber(String),
+    /// The text is not a number, in a game played with decimals.
+    NotADecimal(String),
+    /// The secret is never negative (for whole numbers).
+    Negative,
+    /// The number cannot be the secret because it is outside the game's range.
+    OutOfRange { min: N, max: N },
+    /// This number was already guessed.
+    Repeated(N),
+    /// The code has a different number of digits than this guess.
+    WrongLength { expected: usize, got: usize },
+    /// The code never uses this digit.
+    DigitOutOfRange { digit: u8, highest: u8 },
+    /// The game already ended, so there is nothing left to guess.
+    GameOver,
+}
+
+impl<N: fmt::Display> fmt::Display for GuessError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::Empty => f.write_str(&tr("error_empty")),
+            GuessError::NotANumber(text) => {
+                f.write_str(&tr_with("error_not_a_number", &[("text", text)]))
+            }
+            GuessError::NotADecimal(text) => {
+                f.write_str(&tr_with("error_not_a_decimal", &[("text", text)]))
+            }
+            GuessError::Negative => f.write_str(&tr("error_negative")),
+            GuessError::OutOfRange { min, max } => f.write_str(&tr_with(
+                "error_out_of_range",
+                &[("min", min), ("max", max)],
+            )),
+            GuessError::Repeated(guess) => {
+                f.write_str(&tr_with("error_repeated", &[("guess", guess)]))
+            }
+            GuessError::WrongLength { expected, got } => f.write_str(&tr_with(
+                "error_wrong_length",
+                &[("expected", expected), ("got", got)],
+            )),
+            GuessError::DigitOutOfRange { digit, highest } => f.write_str(&tr_with(
+                "error_digit_out_of_range",
+                &[("digit", digit), ("highest", highest)],
+            )),
+            GuessError::GameOver => f.write_str(&tr("error_game_over")),
+        }
+    }
+}
+
+impl<N: fmt::Debug + fmt::Display> std::error::Error for GuessError<N> {}
+
+/// One turn, as it happened: an accepted guess, or the time for one running out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn<N = u32> {
+    /// `None` when the time for the guess ran out.
+    pub guess: Option<N>,
+    /// How the guess compared with the secret, `None` when it timed out.
+    pub ordering: Option<Ordering>,
+    /// Time since the game started.
+    pub elapsed: Duration,
+    /// Lives left after this guess.
+    pub lives: u32,
+}
+
+impl<N> Turn<N> {
+    /// True when the player took too long and no guess was made.
+    pub fn timed_out(&self) -> bool {
+        self.guess.is_none()
+    }
+}
+
+/// Typed text without the spaces and newline around it, or `Empty` if nothing is left.
+/// Every game's `parse_guess` starts with this.
+pub(crate) fn trimmed<N>(input: &str) -> Result<&str, GuessError<N>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(GuessError::Empty);
+    }
+    Ok(input)
+}
+
+/*
+ * The Game struct is a small state machine:
+ * every call to `guess` moves it forward until it is Won or Lost.
+ * It never reads input or prints anything, so it can be driven by
+ * a terminal, a test or anything else.
+ *
+ * It is generic over N, the kind of number (see number.rs):
+ * the same code plays with u32, i64 or f64, and a plain `Game` means `Game<u32>`.
+ */
+#[derive(Debug, Clone)]
+pub struct Game<N = u32> {
+    settings: Settings<N>,
+    secret: N,
+    lives: u32,
+    guesses: Vec<N>,
+    turns: Vec<Turn<N>>,
+    state: State,
+    started: Instant,
+    /// How long the game took, set when it ends.
+    duration: Option<Duration>,
+}
+
+impl<N: Number> Game<N> {
+    /// Starts a game with a known secret number.
+    pub fn new(settings: Settings<N>, secret: N) -> Self {
+        Self {
+            lives: settings.lives,
+            settings,
+            secret,
+            guesses: Vec::new(),
+            turns: Vec::new(),
+            state: State::Playing,
+            started: Instant::now(),
+            duration: None,
+        }
+    }
+
+    /// Starts a game with a secret picked by `rng` from the settings' range.
+    ///
+    /// Format: rng.random_range(<min_value>..=<max_value>), inside `Number::random`
+    pub fn random<R: Rng + ?Sized>(settings: Settings<N>, rng: &mut R) -> Self {
+        let secret = N::random(rng, settings.min, settings.max);
+        Self::new(settings, secret)
+    }
+
+    /// Turns typed text into a guess, explaining what is wrong with it if it can't be one.
+    pub fn parse_guess(&self, input: &str) -> Result<N, GuessError<N>> {
+        let input = trimmed(input)?;
+
+        let guess = N::parse(input).map_err(|error| match error {
+            ParseError::NotANumber if N::KIND == NumberKind::Decimal => {
+                GuessError::NotADecimal(input.to_string())
+            }
+            ParseError::NotANumber => GuessError::NotANumber(input.to_string()),
+            ParseError::Negative => GuessError::Negative,
+            ParseError::TooBig => self.out_of_range(),
+        })?;
+        self.check(guess)?;
+        Ok(guess)
+    }
+
+    /// Rejects guesses that can't be the secret or were already tried.
+    fn check(&self, guess: N) -> Result<(), GuessError<N>> {
+        if self.state != State::Playing {
+            return Err(GuessError::GameOver);
+        }
+        if guess < self.settings.min || guess > self.settings.max {
+            return Err(self.out_of_range());
+        }
+        if self.guesses.contains(&guess) {
+            return Err(GuessError::Repeated(guess));
+        }
+        Ok(())
+    }
+
+    fn out_of_range(&self) -> GuessError<N> {
+        GuessError::OutOfRange {
+            min: self.settings.min,
+            max: self.settings.max,
+        }
+    }
+
+    /// Compares `guess` with the secret number.
+    ///
+    /// A wrong guess costs a life; running out of lives loses the game.
+    /// Guesses outside the range or already tried are rejected without costing anything.
+    /// A guess within the settings' tolerance of the secret counts as Equal.
+    pub fn guess(&mut self, guess: N) -> Result<Ordering, GuessError<N>> {
+        self.check(guess)?;
+
+        self.guesses.push(guess);
+
+        // Ordering is an enum with three variants: Less, Greater, and Equal
+        let ordering = self.compare(guess);
+        match ordering {
+            Ordering::Equal => self.state = State::Won,
+            Ordering::Less | Ordering::Greater => {
+                self.lives -= 1;
+                if self.lives == 0 {
+                    self.state = State::Lost;
+                }
+            }
+        }
+
+        self.record(Some(guess), Some(ordering));
+        Ok(ordering)
+    }
+
+    /*
+     * u32 has `cmp`, but f64 only has `partial_cmp`: NaN can't be compared with anything,
+     * so the answer is an Option. Guesses are never NaN (Number::parse rejects it),
+     * so a missing answer never happens; treating it as Equal keeps `compare` simple.
+     */
+    /// Compares `guess` with the secret, allowing for the tolerance.
+    fn compare(&self, guess: N) -> Ordering {
+        if guess.within(self.secret, self.settings.tolerance) {
+            return Ordering::Equal;
+        }
+        guess.partial_cmp(&self.secret).unwrap_or(Ordering::Equal)
+    }
+
+    /// The time for a guess ran out: this costs a life, just like a wrong guess,
+    /// and is recorded as a turn without a guess.
+    pub fn miss(&mut self) {
+        if self.state != State::Playing {
+            return;
+        }
+
+        self.lives -= 1;
+        if self.lives == 0 {
+            self.state = State::Lost;
+        }
+        self.record(None, None);
+    }
+
+    /// Adds a turn, and stops the clock if it ended the game.
+    fn record(&mut self, guess: Option<N>, ordering: Option<Ordering>) {
+        self.turns.push(Turn {
+            guess,
+            ordering,
+            elapsed: self.started.elapsed(),
+            lives: self.lives,
+        });
+        if self.is_over() {
+            self.duration = Some(self.started.elapsed());
+        }
+    }
+
+    /// Ends the game as a loss, e.g. when the time limit runs out.
+    pub fn forfeit(&mut self) {
+        if self.state == State::Playing {
+            self.state = State::Lost;
+            self.duration = Some(self.started.elapsed());
+        }
+    }
+
+    /// Ends the game without a result, when nobody is left to play it.
+    pub fn abandon(&mut self) {
+        if self.state == State::Playing {
+            self.state = State::Abandoned;
+            self.duration = Some(self.started.elapsed());
+        }
+    }
+
+    pub fn settings(&self) -> &Settings<N> {
+        &self.settings
+    }
+
+    pub fn secret(&self) -> N {
+        self.secret
+    }
+
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    /// Every accepted guess, oldest first.
+    pub fn guesses(&self) -> &[N] {
+        &self.guesses
+    }
+
+    /// Every turn with its guess, result and timing, oldest first.
+    /// Turns that timed out are included, so there can be more turns than guesses.
+    pub fn turns(&self) -> &[Turn<N>] {
+        &self.turns
+    }
+
+    /// How long the game took, or has taken so far if it is still going.
+    pub fn elapsed(&self) -> Duration {
+        self.duration.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.state != State::Playing
+    }
+}
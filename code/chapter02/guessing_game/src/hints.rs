@@ -0,0 +1,109 @@
+/*
+ * Proximity hints for a gentler game.
+ * "Too small!"/"Too big!" only says which side the secret is on;
+ * these also say how close the guess was and whether it got closer.
+ */
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::i18n::{tr, tr_with};
+use crate::number::Number;
+
+/// How the latest guess compares with the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Temperature {
+    Warmer,
+    Colder,
+    Same,
+}
+
+/// A coarse distance between the guess and the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Band {
+    Within5,
+    Within20,
+    Within50,
+    Far,
+}
+
+impl Band {
+    pub fn from_distance(distance: u32) -> Self {
+        match distance {
+            0..=5 => Band::Within5,
+            6..=20 => Band::Within20,
+            21..=50 => Band::Within50,
+            _ => Band::Far,
+        }
+    }
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            Band::Within5 => "hint_within_5",
+            Band::Within20 => "hint_within_20",
+            Band::Within50 => "hint_within_50",
+            Band::Far => "hint_far",
+        };
+        f.write_str(&tr(key))
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            Temperature::Warmer => "hint_warmer",
+            Temperature::Colder => "hint_colder",
+            Temperature::Same => "hint_same",
+        };
+        f.write_str(&tr(key))
+    }
+}
+
+/// The hint for the latest of `guesses`: how far it is, and whether it beat the guess before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub band: Band,
+    /// `None` for the first guess, which has nothing to compare with.
+    pub temperature: Option<Temperature>,
+}
+
+impl Hint {
+    /// `None` when there are no guesses yet or the latest one is the secret.
+    pub fn for_any<N: Number>(secret: N, guesses: &[N]) -> Option<Self> {
+        let (&latest, earlier) = guesses.split_last()?;
+        // Compared as N, not as the f64 distance, which can't tell huge i64 neighbours apart.
+        if latest == secret {
+            return None;
+        }
+        // distance is always positive, without worrying about which number is bigger.
+        let distance = latest.distance(secret);
+
+        let temperature =
+            earlier.last().map(
+                |&previous| match distance.total_cmp(&previous.distance(secret)) {
+                    Ordering::Less => Temperature::Warmer,
+                    Ordering::Greater => Temperature::Colder,
+                    Ordering::Equal => Temperature::Same,
+                },
+            );
+
+        // Rounded up, so 5.5 away is not "within 5".
+        Some(Self {
+            band: Band::from_distance(distance.ceil() as u32),
+            temperature,
+        })
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.temperature {
+            Some(temperature) => f.write_str(&tr_with(
+                "hint_temperature",
+                &[("temperature", &temperature), ("band", &self.band)],
+            )),
+            None => f.write_str(&tr_with("hint", &[("band", &self.band)])),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * Internationalization ("i18n": i, 18 letters, n): showing the game in the player's language.
+ *
+ * Every message lives in a catalogue, one TOML file per language in locales/,
+ * looked up by a key like "too_small". Values can contain {placeholders}:
+ *
+ *     you_guessed = "You guessed: {guess}"
+ *
+ * include_str! copies the files into the program when it is compiled,
+ * so the binary works without the locales folder next to it.
+ * A key missing from a translation falls back to English, so a half-finished
+ * translation still works; a key missing from English shows the key itself.
+ */
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The languages with a catalogue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    Spanish,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Spanish, Language::German];
+
+    /// The two-letter code: "en", "es" or "de".
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+        }
+    }
+
+    /// Reads a locale like "es", "de-AT" or "es_ES.UTF-8" by its language subtag,
+    /// the letters before the first `_`, `-` or `.`.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let subtag = locale.split(['_', '-', '.']).next()?;
+        // Two letters exactly, so "desktop" or "english" isn't read as "de" or "en".
+        if subtag.len() != 2 || !subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let code = subtag.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// The language asked for by the environment, the same variables other programs read:
+    /// `LC_ALL` wins over `LC_MESSAGES`, which wins over `LANG`.
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_locale(s.trim()).ok_or_else(|| {
+            let codes: Vec<&str> = Self::ALL.iter().map(|language| language.code()).collect();
+            format!(
+                "unknown language '{s}', choose one of: {}",
+                codes.join(", ")
+            )
+        })
+    }
+}
+
+/// Every message in every language.
+#[derive(Debug, Clone, Default)]
+pub struct Catalogue {
+    messages: HashMap<Language, HashMap<String, String>>,
+}
+
+impl Catalogue {
+    /// Reads one TOML file of `key = "message"` lines per language.
+    pub fn parse(sources: &[(Language, &str)]) -> Result<Self, String> {
+        let mut messages = HashMap::new();
+        for &(language, source) in sources {
+            let table: HashMap<String, String> = toml::from_str(source)
+                .map_err(|error| format!("in the {language} messages: {error}"))?;
+            messages.insert(language, table);
+        }
+        Ok(Self { messages })
+    }
+
+    /// The catalogue built into the program.
+    pub fn builtin() -> &'static Catalogue {
+        // OnceLock runs the closure the first time only, later calls get the same catalogue.
+        static BUILTIN: OnceLock<Catalogue> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            Catalogue::parse(&[
+                (Language::English, include_str!("../locales/en.toml")),
+                (Language::Spanish, include_str!("../locales/es.toml")),
+                (Language::German, include_str!("../locales/de.toml")),
+            ])
+            .expect("the built-in messages are valid TOML")
+        })
+    }
+
+    /// The message for `key` in `language`, or in English if it hasn't been translated.
+    pub fn get<'a>(&'a self, language: Language, key: &'a str) -> &'a str {
+        [language, Language::English]
+            .iter()
+            .find_map(|language| self.messages.get(language)?.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// The keys `language` has that English doesn't: probably typos.
+    pub fn unknown_keys(&self, language: Language) -> Vec<&str> {
+        let (Some(english), Some(messages)) = (
+            self.messages.get(&Language::English),
+            self.messages.get(&language),
+        ) else {
+            return Vec::new();
+        };
+        let mut keys: Vec<&str> = messages
+            .keys()
+            .filter(|key| !english.contains_key(*key))
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+// The language is chosen once for the whole program, like colour (see color.rs).
+static LANGUAGE: AtomicUsize = AtomicUsize::new(0);
+
+/// Switches every message to `language`. It starts in English.
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(0);
+    LANGUAGE.store(index, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL[LANGUAGE.load(Ordering::Relaxed)]
+}
+
+/// The message for `key` in the current language.
+pub fn tr(key: &str) -> String {
+    tr_with(key, &[])
+}
+
+/// The message for `key` in the current language, with each `{name}` replaced by its value.
+///
+/// `&dyn fmt::Display` lets one slice hold values of different types, like numbers and text.
+pub fn tr_with(key: &str, values: &[(&str, &dyn fmt::Display)]) -> String {
+    fill(Catalogue::builtin().get(language(), key), values)
+}
+
+/// True when `answer` is one of the comma-separated words in the message for `key`,
+/// like "j, ja" in German. The answer should already be lowercase and trimmed.
+pub fn is_listed(key: &str, answer: &str) -> bool {
+    tr(key).split(',').any(|word| word.trim() == answer)
+}
+
+/// Replaces each `{name}` in `template` with its value. Unknown names are left as they are.
+///
+/// The template is read once from left to right, so a value is never searched again:
+/// a guess typed as `{error}` stays `{error}` instead of turning into the error message.
+pub fn fill(template: &str, values: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            let (_, value) = values.iter().find(|(key, _)| *key == name)?;
+            Some((value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                text.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
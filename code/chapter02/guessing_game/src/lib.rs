@@ -0,0 +1,32 @@
+/*
+ * A package can have both a library crate (src/lib.rs) and a binary crate (src/main.rs).
+ * The library holds the game itself, the binary only wires it to the terminal.
+ * Tests in the tests/ folder use the library just like main.rs does.
+ *
+ * pub mod: declares a module and makes it visible outside this crate.
+ */
+pub mod adaptive;
+pub mod batch;
+pub mod cli;
+pub mod codebreaker;
+pub mod color;
+pub mod config;
+pub mod difficulty;
+pub mod game;
+pub mod hints;
+pub mod i18n;
+pub mod net;
+pub mod number;
+pub mod play;
+pub mod reverse;
+pub mod scores;
+pub mod server;
+pub mod solver;
+pub mod timed;
+pub mod tournament;
+pub mod transcript;
+pub mod tui;
+
+pub use difficulty::{Difficulty, Settings};
+pub use game::{Game, GuessError, State, Turn};
+pub use number::{Number, NumberKind};
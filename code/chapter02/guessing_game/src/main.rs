@@ -4,67 +4,588 @@
  * std: standard library
  * io: input/output library from standard (std) library
  */
-use std::cmp::Ordering;
-use std::io;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::process;
 /*
  * Rng: random number generator
  * rand::Rng: random number generator from rand crate
+ * SeedableRng: generators that can be started from a seed we choose
+ * StdRng: the standard seedable generator, same seed gives the same numbers
  */
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/*
+ * guessing_game is our own library crate (src/lib.rs).
+ * The binary crate uses it the same way it uses rand.
+ */
+use guessing_game::adaptive::{self, Adaptation};
+use guessing_game::batch;
+use guessing_game::cli::Command;
+use guessing_game::codebreaker::Codebreaker;
+use guessing_game::color;
+use guessing_game::config;
+use guessing_game::i18n::{self, Language, tr, tr_with};
+use guessing_game::net::{self, Connection};
+use guessing_game::reverse;
+use guessing_game::scores::{self, Score};
+use guessing_game::server::Server;
+use guessing_game::solver::{self, Benchmark};
+use guessing_game::timed::{self, TimedInput};
+use guessing_game::tournament::Scoreboard;
+use guessing_game::transcript::{self, Summary};
+use guessing_game::tui;
+use guessing_game::{Difficulty, Game, Number, NumberKind, Settings, State, cli, play};
 
 /*
  * The main function is the entry point for a executable rust program.
  * println!: a macro that prints text to the console.
  */
 fn main() {
-    println!("Guess the number!");
+    let mut options = match cli::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => exit_with_usage(&message),
+    };
+
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    if let Some(path) = options.config_path() {
+        let config = config::load(&path, options.config.is_some())
+            .unwrap_or_else(|message| exit_with_usage(&message));
+        options
+            .apply_config(config)
+            .unwrap_or_else(|message| exit_with_usage(&message));
+    }
+
+    i18n::set_language(
+        options
+            .lang
+            .or_else(Language::from_env)
+            .unwrap_or(Language::English),
+    );
+
+    // Escape codes only make sense on a terminal. https://no-color.org asks us to respect NO_COLOR.
+    color::set_enabled(
+        options.color.unwrap_or_else(|| {
+            io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+        }),
+    );
+
+    if options.show_scores {
+        show_scores(&options);
+        return;
+    }
+
+    if options.explain_difficulty {
+        explain_difficulty(&options);
+        return;
+    }
+
+    if options.reset_difficulty {
+        reset_difficulty(&options);
+        return;
+    }
+
+    if options.is_batch() {
+        batch(&options);
+    }
+
+    println!("{}", tr("title"));
+
+    // Locking stdin once gives us a BufRead we can hand to the library.
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout();
+
+    if let Command::Join(address) = &options.command {
+        join(address, &mut input, &mut output);
+        return;
+    }
+
+    if options.command == Command::Codebreaker {
+        codebreaker(&options, input, &mut output);
+        return;
+    }
+
+    /*
+     * `::<i64>` picks the type parameter by hand ("turbofish"):
+     * from here on the same generic code plays with a different kind of number.
+     */
+    match options.numbers {
+        NumberKind::Whole => {}
+        NumberKind::Signed => return play_numbers::<i64>(&options, input, output),
+        NumberKind::Decimal => return play_numbers::<f64>(&options, input, output),
+    }
+
+    let mut settings: Settings = choose_settings(&options, &mut input, &mut output);
+
+    // Only the classic game is scored, so only it has a history to adapt to.
+    let scored = options.command == Command::Play && !options.auto && options.benchmark.is_none();
+    if scored && options.adaptive == Some(true) {
+        settings = adapt(&options, settings);
+    }
+
+    println!("{}", tr_with("playing", &[("settings", &settings)]));
+
+    if options.command == Command::Reverse {
+        if let Err(error) = reverse::play(&settings, &mut input, &mut output) {
+            exit(&error);
+        }
+        return;
+    }
+
+    let mut rng = seeded_rng(&options);
+
+    if let Command::Host(address) = &options.command {
+        host(address, settings, &mut rng, &mut input, &mut output);
+        return;
+    }
+
+    if let Command::Serve(address) = &options.command {
+        serve(address, settings, rng);
+        return;
+    }
+
+    if let Some(games) = options.benchmark {
+        let benchmark = Benchmark::run(settings, games, &mut rng);
+        if let Err(error) = benchmark.write_report(&mut output) {
+            exit(&error);
+        }
+        return;
+    }
+
+    if options.auto {
+        let mut game = Game::random(settings, &mut rng);
+        if let Err(error) = solver::auto_play(&mut game, &mut output) {
+            exit(&error);
+        }
+        return;
+    }
 
     /*
-     * In the first line, we call the rand::thread_rng function that gives us
-     * the particular random number generator we’re going to use:
-     * one that is local to the current thread of execution and is seeded by the operating system
-     *
-     * Format: rand::rng().random_range(<min_value>..=<max_value>)
+     * Functions can be passed around like values: each branch hands
+     * play_session the function that plays one round in its own style.
      */
-    let secret_number = rand::rng().random_range(1..=100);
+    if options.tui {
+        play_tui(&options, &settings, &mut rng, input, &mut output);
+    } else {
+        play_lines(&options, &settings, &mut rng, input, &mut output);
+    }
+}
+
+/// The settings from the command line, or else from the menu.
+fn choose_settings<N: Number, R: BufRead, W: Write>(
+    options: &cli::Options,
+    input: &mut R,
+    output: &mut W,
+) -> Settings<N> {
+    let mut settings = match options.settings_for() {
+        Ok(Some(settings)) => settings,
+        // Nobody is at the keyboard to answer the menu when the computer plays.
+        Ok(None) if options.auto || options.benchmark.is_some() => {
+            Difficulty::Normal.settings().to()
+        }
+        Ok(None) => play::choose_settings(input, output)
+            .unwrap_or_else(|error| exit(&error))
+            .to(),
+        Err(message) => exit_with_usage(&message),
+    };
+
+    settings.hints = options.hints.unwrap_or(false);
+    settings
+}
+
+/// Plays the classic game with signed or decimal numbers (`--numbers`).
+fn play_numbers<N: AfterRound>(
+    options: &cli::Options,
+    mut input: io::StdinLock<'static>,
+    mut output: io::Stdout,
+) {
+    let settings: Settings<N> = choose_settings(options, &mut input, &mut output);
+    println!("{}", tr_with("playing", &[("settings", &settings)]));
+    play_lines(
+        options,
+        &settings,
+        &mut seeded_rng(options),
+        input,
+        &mut output,
+    );
+}
+
+/// Plays line by line, the default so piped or scripted games keep working.
+fn play_lines<N: AfterRound>(
+    options: &cli::Options,
+    settings: &Settings<N>,
+    rng: &mut StdRng,
+    mut input: io::StdinLock<'static>,
+    output: &mut io::Stdout,
+) {
+    if settings.is_timed() {
+        // The reader thread needs stdin to itself, so we let go of our lock first.
+        drop(input);
+        let mut input = TimedInput::spawn(io::BufReader::new(io::stdin()));
+        play_session(options, settings, rng, &mut input, output, timed::play);
+    } else {
+        play_session(options, settings, rng, &mut input, output, play::play);
+    }
+}
+
+/// Plays on the full-screen view, with the clocks running if the settings have any.
+fn play_tui(
+    options: &cli::Options,
+    settings: &Settings,
+    rng: &mut StdRng,
+    mut input: io::StdinLock<'static>,
+    output: &mut io::Stdout,
+) {
+    if settings.is_timed() {
+        drop(input);
+        let mut input = TimedInput::spawn(io::BufReader::new(io::stdin()));
+        play_session(options, settings, rng, &mut input, output, tui::play_timed);
+    } else {
+        play_session(options, settings, rng, &mut input, output, tui::play);
+    }
+}
+
+/*
+ * The summary, transcript, score file and scoreboard all store whole numbers,
+ * so only u32 games are recorded. A trait with a default method that does nothing
+ * lets play_session call `N::after_round` without knowing which kind it has.
+ */
+/// What is kept of each finished round.
+trait AfterRound: Number {
+    fn after_round(_options: &cli::Options, _game: &Game<Self>, _scoreboard: &mut Scoreboard) {}
+}
+
+impl AfterRound for u32 {
+    fn after_round(options: &cli::Options, game: &Game, scoreboard: &mut Scoreboard) {
+        println!("{}", Summary::new(game));
+        save_transcript(options, game);
+        // An abandoned game has no result, so it is neither a score nor a round.
+        if game.state() != State::Abandoned {
+            save_score(options, game);
+            scoreboard.record(game);
+        }
+    }
+}
+
+impl AfterRound for i64 {}
 
-    let mut lives = 5;
+impl AfterRound for f64 {}
+
+/// Plays rounds until `--rounds` are done or the player doesn't want another one.
+fn play_session<N, R, W>(
+    options: &cli::Options,
+    settings: &Settings<N>,
+    rng: &mut StdRng,
+    input: &mut R,
+    output: &mut W,
+    mut play_round: impl FnMut(&mut Game<N>, &mut R, &mut W) -> io::Result<State>,
+) where
+    N: AfterRound,
+    R: BufRead,
+    W: Write,
+{
+    let mut scoreboard = Scoreboard::default();
+    let mut round = 0;
 
     loop {
-        if lives == 0 {
-            println!("You lose! The secret number was {secret_number}.");
-            break;
-        } else {
-            println!("Please input your guess. You have {lives} lives left.");
+        round += 1;
+        if let Some(rounds) = options.rounds {
+            println!(
+                "{}",
+                tr_with("round_of", &[("round", &round), ("rounds", &rounds)])
+            );
         }
 
-        /*
-         * Create a mutable, empty String to store user input.
-         * String::new() is an associated function.
-         */
-        let mut guess = String::new();
+        let mut game = Game::random(settings.clone(), rng);
+        if let Err(error) = play_round(&mut game, input, output) {
+            exit(&error);
+        }
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+        N::after_round(options, &game, &mut scoreboard);
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+        // The input ran out, so nobody is left to play another round.
+        let another = match options.rounds {
+            _ if game.state() == State::Abandoned => false,
+            Some(rounds) => round < rounds,
+            None => play::play_again(input, output).unwrap_or_else(|error| exit(&error)),
         };
+        if !another {
+            break;
+        }
+    }
+
+    if !scoreboard.rounds().is_empty() && (options.rounds.is_some() || round > 1) {
+        println!("\n{scoreboard}");
+    }
+}
+
+/*
+ * rand::rng() gives us the random number generator that is
+ * local to the current thread of execution and is seeded by the operating system.
+ * We only use it to pick a seed, then build our own StdRng from that seed.
+ * Printing the seed means any game can be replayed with `--seed <N>`.
+ */
+fn seeded_rng(options: &cli::Options) -> StdRng {
+    let seed = match options.seed() {
+        Ok(Some(seed)) => seed,
+        Ok(None) => rand::rng().random(),
+        Err(message) => exit_with_usage(&message),
+    };
+    note(options, &tr_with("seed", &[("seed", &seed)]));
+    StdRng::seed_from_u64(seed)
+}
+
+/// Prints a message for the player. In batch mode stdout is only for JSON, so it goes to stderr.
+fn note(options: &cli::Options, message: &str) {
+    if options.is_batch() {
+        eprintln!("{message}");
+    } else {
+        println!("{message}");
+    }
+}
+
+/// Plays the guesses from `--guesses` or stdin and exits with the result.
+fn batch(options: &cli::Options) -> ! {
+    let settings = match options.settings_for::<u32>() {
+        Ok(settings) => settings.unwrap_or_else(|| Difficulty::Normal.settings()),
+        Err(message) => exit_with_usage(&message),
+    };
+    let mut game = Game::random(settings, &mut seeded_rng(options));
 
-        println!("You guessed: {guess}");
+    // Box<dyn Iterator> lets both kinds of guesses go into the same variable.
+    let guesses: Box<dyn Iterator<Item = String>> = match &options.guesses {
+        Some(list) => Box::new(list.clone().into_iter()),
+        // Lines are read one by one, so a script can answer each result as it comes.
+        None => Box::new(
+            io::stdin()
+                .lines()
+                .map_while(Result::ok)
+                .flat_map(|line| batch::parse_list(&line)),
+        ),
+    };
 
-        // Ordering is an enum with three variants: Less, Greater, and Equal
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
-            }
+    let state =
+        batch::run(&mut game, guesses, &mut io::stdout()).unwrap_or_else(|error| exit(&error));
+    save_transcript(options, &game);
+    process::exit(batch::exit_code(state));
+}
+
+/// Plays the code-breaking game with the same loops as the number game.
+fn codebreaker(options: &cli::Options, mut input: io::StdinLock<'static>, output: &mut io::Stdout) {
+    let rules = options
+        .codebreaker_rules()
+        .unwrap_or_else(|message| exit_with_usage(&message));
+    println!("{}", tr_with("codebreaker_intro", &[("rules", &rules)]));
+    println!("{}", tr("codebreaker_explain"));
+
+    let timed = rules.time_limit.is_some() || rules.turn_limit.is_some();
+    let mut game = Codebreaker::random(rules, &mut seeded_rng(options));
+    let result = if timed {
+        // Like play_lines: the reader thread needs stdin to itself.
+        drop(input);
+        let mut input = TimedInput::spawn(io::BufReader::new(io::stdin()));
+        timed::play(&mut game, &mut input, output)
+    } else {
+        play::play(&mut game, &mut input, output)
+    };
+    if let Err(error) = result {
+        exit(&error);
+    }
+}
+
+/// Writes the `--transcript` file, if one was asked for. Failing to save is only a warning.
+fn save_transcript(options: &cli::Options, game: &Game) {
+    let Some(path) = &options.transcript else {
+        return;
+    };
+
+    match transcript::save(path, game, &options.player_name()) {
+        Ok(()) => note(
+            options,
+            &tr_with("transcript_saved", &[("path", &path.display())]),
+        ),
+        Err(error) => eprintln!(
+            "warning: could not save the transcript to {}: {error}",
+            path.display()
+        ),
+    }
+}
+
+/// Waits for one guest on `address` and judges their guesses.
+fn host<R: BufRead, W: Write>(
+    address: &str,
+    settings: Settings,
+    rng: &mut StdRng,
+    input: &mut R,
+    output: &mut W,
+) {
+    let secret =
+        net::choose_secret(&settings, rng, input, output).unwrap_or_else(|error| exit(&error));
+
+    let listener = TcpListener::bind(address).unwrap_or_else(|error| exit(&error));
+    println!("{}", tr_with("host_waiting", &[("address", &address)]));
+    let (stream, guest) = listener.accept().unwrap_or_else(|error| exit(&error));
+    println!("{}", tr_with("host_joined", &[("guest", &guest)]));
+
+    let mut game = Game::new(settings, secret);
+    let result = Connection::new(stream)
+        .and_then(|mut connection| net::host(&mut connection, &mut game, output));
+    if let Err(error) = result {
+        exit(&error);
+    }
+}
+
+/// Serves the game to browsers on `address` until the program is stopped.
+fn serve(address: &str, settings: Settings, rng: StdRng) {
+    let listener = TcpListener::bind(address).unwrap_or_else(|error| exit(&error));
+    // With port 0 the system picks a free port, so ask the listener which one it was.
+    let address = listener.local_addr().unwrap_or_else(|error| exit(&error));
+    println!("{}", tr_with("serve_open", &[("address", &address)]));
+
+    if let Err(error) = Server::new(settings, rng).serve(&listener) {
+        exit(&error);
+    }
+}
+
+/// Connects to a host on `address` and guesses their number.
+fn join<R: BufRead, W: Write>(address: &str, input: &mut R, output: &mut W) {
+    println!("{}", tr_with("join_joining", &[("address", &address)]));
+    let stream = TcpStream::connect(address).unwrap_or_else(|error| exit(&error));
+
+    let result = Connection::new(stream)
+        .and_then(|mut connection| net::join(&mut connection, input, output));
+    if let Err(error) = result {
+        exit(&error);
+    }
+}
+
+/// Prints the leaderboard, only for one difficulty if `--difficulty` was given.
+fn show_scores(options: &cli::Options) {
+    let Some(path) = options.scores_path() else {
+        exit_with_usage("no home folder found, use --scores-file to choose where scores are kept");
+    };
+
+    let mut scores = scores::load(&path).unwrap_or_else(|error| exit(&error));
+    if let Some(difficulty) = options.difficulty {
+        scores.retain(|score| score.difficulty == difficulty);
+    }
+    scores::rank(&mut scores);
+
+    if let Err(error) = scores::write_leaderboard(&mut io::stdout(), &scores) {
+        exit(&error);
+    }
+}
+
+/// The scores `name` has in `path` since their last `--reset-difficulty`.
+fn player_history(path: &Path, name: &str) -> io::Result<Vec<Score>> {
+    let scores = scores::load(path)?;
+    let reset = adaptive::load_reset(&adaptive::resets_path(path), name)?;
+    Ok(adaptive::history(&scores, name, reset))
+}
+
+/// `settings` made harder or easier by the player's history. Without one, they stay as they are.
+fn adapt(options: &cli::Options, settings: Settings) -> Settings {
+    let Some(path) = options.scores_path() else {
+        eprintln!("warning: no home folder found, adaptive difficulty is off");
+        return settings;
+    };
+
+    match player_history(&path, &options.player_name()) {
+        Ok(history) => {
+            let adaptation = Adaptation::learn(&history);
+            println!(
+                "{}",
+                tr_with("adaptive_level", &[("level", &adaptation.level)])
+            );
+            adaptation.apply(&settings)
         }
+        Err(error) => {
+            eprintln!("warning: could not read the scores, adaptive difficulty is off: {error}");
+            settings
+        }
+    }
+}
+
+/// Prints how the player's history sets their adaptive level.
+fn explain_difficulty(options: &cli::Options) {
+    let Some(path) = options.scores_path() else {
+        exit_with_usage("no home folder found, use --scores-file to choose where scores are kept");
+    };
+    let base = match options.settings_for::<u32>() {
+        Ok(settings) => settings.unwrap_or_else(|| Difficulty::Normal.settings()),
+        Err(message) => exit_with_usage(&message),
+    };
+
+    let name = options.player_name();
+    let history = player_history(&path, &name).unwrap_or_else(|error| exit(&error));
+    let adaptation = Adaptation::learn(&history);
+
+    println!("{}", tr_with("adaptive_title", &[("name", &name)]));
+    println!("{adaptation}");
+    // The labels are padded to the same width, whatever the language makes them.
+    let (base_label, adapted_label) = (tr("adaptive_base"), tr("adaptive_adapted"));
+    let width = base_label
+        .chars()
+        .count()
+        .max(adapted_label.chars().count())
+        + 1;
+    println!("  {:<width$} {base}", format!("{base_label}:"));
+    println!(
+        "  {:<width$} {}",
+        format!("{adapted_label}:"),
+        adaptation.apply(&base)
+    );
+    println!("{}", tr("adaptive_reset_hint"));
+}
+
+/// Starts the player's adaptive level over: the games played so far stop counting.
+fn reset_difficulty(options: &cli::Options) {
+    let Some(path) = options.scores_path() else {
+        exit_with_usage("no home folder found, use --scores-file to choose where scores are kept");
+    };
+
+    let name = options.player_name();
+    let scores = scores::load(&path).unwrap_or_else(|error| exit(&error));
+    let games = adaptive::history(&scores, &name, 0).len();
+    adaptive::save_reset(&adaptive::resets_path(&path), &name, games)
+        .unwrap_or_else(|error| exit(&error));
+
+    println!("{}", tr_with("adaptive_reset_done", &[("name", &name)]));
+}
+
+/// Adds the finished game to the score file. Failing to save is only a warning.
+fn save_score(options: &cli::Options, game: &Game) {
+    let Some(path) = options.scores_path() else {
+        eprintln!("warning: no home folder found, the score was not saved");
+        return;
+    };
 
-        lives -= 1;
+    let score = Score::from_game(&options.player_name(), game);
+    match scores::append(&path, &score) {
+        Ok(()) => println!("{}", tr_with("score_saved", &[("name", &score.name)])),
+        Err(error) => eprintln!(
+            "warning: could not save the score to {}: {error}",
+            path.display()
+        ),
     }
 }
+
+/*
+ * The `!` return type means this function never returns:
+ * process::exit stops the whole program.
+ */
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("error: {message}\n\n{}", cli::USAGE);
+    process::exit(2);
+}
+
+fn exit(error: &io::Error) -> ! {
+    eprintln!("error: {error}");
+    process::exit(1);
+}
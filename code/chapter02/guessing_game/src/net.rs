@@ -0,0 +1,304 @@
+/*
+ * Two players over TCP: the host picks the secret, the guest guesses.
+ *
+ * The protocol is one message per line of text, so it can be read with read_line
+ * and even typed by hand with `nc 127.0.0.1 7878`:
+ *
+ *     host -> guest   HELLO <min> <max> <lives>   the game's rules
+ *     host -> guest   TURN <lives>                your turn to guess
+ *     guest -> host   GUESS <text>                what the guest typed
+ *     host -> guest   RESULT LESS|GREATER|EQUAL   the guess compared with the secret
+ *     host -> guest   INVALID <message>           the guess was rejected, no life lost
+ *     host -> guest   HINT <message>              proximity hint, when hints are on
+ *     host -> guest   OVER WIN|LOSE <secret>      the game ended
+ *
+ * The host owns the Game, so only the host decides what costs a life.
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use rand::Rng;
+
+use crate::difficulty::Settings;
+use crate::game::{Game, State};
+use crate::hints::Hint;
+use crate::i18n::{tr, tr_with};
+use crate::play::{not_counted, prompt};
+use crate::transcript::result_key;
+
+/// Where `host` listens and `join` connects when no address is given.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// One line of the protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Hello { min: u32, max: u32, lives: u32 },
+    Turn { lives: u32 },
+    Guess(String),
+    Result(Ordering),
+    Invalid(String),
+    Hint(String),
+    Over { won: bool, secret: u32 },
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Hello { min, max, lives } => write!(f, "HELLO {min} {max} {lives}"),
+            Message::Turn { lives } => write!(f, "TURN {lives}"),
+            Message::Guess(text) => write!(f, "GUESS {text}"),
+            Message::Result(ordering) => {
+                let name = match ordering {
+                    Ordering::Less => "LESS",
+                    Ordering::Greater => "GREATER",
+                    Ordering::Equal => "EQUAL",
+                };
+                write!(f, "RESULT {name}")
+            }
+            Message::Invalid(text) => write!(f, "INVALID {text}"),
+            Message::Hint(text) => write!(f, "HINT {text}"),
+            Message::Over { won, secret } => {
+                write!(f, "OVER {} {secret}", if *won { "WIN" } else { "LOSE" })
+            }
+        }
+    }
+}
+
+impl Message {
+    /// Reads one protocol line. The error says what was wrong with it.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let numbers = || -> Result<Vec<u32>, String> {
+            rest.split_whitespace()
+                .map(|word| word.parse().map_err(|_| format!("bad number in '{line}'")))
+                .collect()
+        };
+
+        let message = match kind {
+            "HELLO" => match numbers()?[..] {
+                [min, max, lives] => Message::Hello { min, max, lives },
+                _ => return Err(format!("HELLO needs 3 numbers: '{line}'")),
+            },
+            "TURN" => match numbers()?[..] {
+                [lives] => Message::Turn { lives },
+                _ => return Err(format!("TURN needs 1 number: '{line}'")),
+            },
+            "GUESS" => Message::Guess(rest.to_string()),
+            "RESULT" => Message::Result(match rest {
+                "LESS" => Ordering::Less,
+                "GREATER" => Ordering::Greater,
+                "EQUAL" => Ordering::Equal,
+                _ => return Err(format!("unknown result '{rest}'")),
+            }),
+            "INVALID" => Message::Invalid(rest.to_string()),
+            "HINT" => Message::Hint(rest.to_string()),
+            "OVER" => match rest.split_once(' ') {
+                Some((result @ ("WIN" | "LOSE"), secret)) => Message::Over {
+                    won: result == "WIN",
+                    secret: secret
+                        .parse()
+                        .map_err(|_| format!("bad secret in '{line}'"))?,
+                },
+                _ => return Err(format!("OVER needs WIN or LOSE and the secret: '{line}'")),
+            },
+            _ => return Err(format!("unknown message '{line}'")),
+        };
+
+        Ok(message)
+    }
+}
+
+/// A TCP stream that sends and receives whole `Message`s.
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        // try_clone gives a second handle to the same socket: one to read, one to write.
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    pub fn send(&mut self, message: &Message) -> io::Result<()> {
+        writeln!(self.writer, "{message}")?;
+        self.writer.flush()
+    }
+
+    /// Waits for the next message. A closed connection is a `ConnectionAborted` error.
+    pub fn receive(&mut self) -> io::Result<Message> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "the other player disconnected",
+            ));
+        }
+        Message::parse(&line).map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))
+    }
+}
+
+/// Asks the host for the secret number. An empty answer lets `rng` pick one.
+pub fn choose_secret<R, W, G>(
+    settings: &Settings,
+    rng: &mut G,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<u32>
+where
+    R: BufRead,
+    W: Write,
+    G: Rng + ?Sized,
+{
+    let question = tr_with(
+        "host_choose_secret",
+        &[("min", &settings.min), ("max", &settings.max)],
+    );
+
+    loop {
+        let answer = prompt(input, output, &question)?;
+        if answer.is_empty() {
+            return Ok(rng.random_range(settings.min..=settings.max));
+        }
+
+        // An empty game is only used to check the answer with the same rules as guesses.
+        match Game::new(settings.clone(), settings.min).parse_guess(&answer) {
+            Ok(secret) => return Ok(secret),
+            Err(error) => writeln!(output, "{error}")?,
+        }
+    }
+}
+
+/// Runs the host's side: sends the rules, then judges guesses until `game` ends.
+pub fn host<W: Write>(
+    connection: &mut Connection,
+    game: &mut Game,
+    output: &mut W,
+) -> io::Result<State> {
+    let settings = game.settings().clone();
+    connection.send(&Message::Hello {
+        min: settings.min,
+        max: settings.max,
+        lives: settings.lives,
+    })?;
+
+    while !game.is_over() {
+        connection.send(&Message::Turn {
+            lives: game.lives(),
+        })?;
+
+        let text = match connection.receive()? {
+            Message::Guess(text) => text,
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected a guess, got '{other}'"),
+                ));
+            }
+        };
+
+        let guess = match game.parse_guess(&text) {
+            Ok(guess) => guess,
+            Err(error) => {
+                writeln!(
+                    output,
+                    "{}",
+                    tr_with("host_invalid", &[("text", &text), ("error", &error)])
+                )?;
+                connection.send(&Message::Invalid(error.to_string()))?;
+                continue;
+            }
+        };
+
+        let ordering = game.guess(guess).map_err(io::Error::other)?;
+        writeln!(
+            output,
+            "{}",
+            tr_with(
+                "host_guessed",
+                &[
+                    ("guess", &guess),
+                    ("result", &tr(result_key(ordering))),
+                    ("lives", &game.lives())
+                ]
+            )
+        )?;
+        connection.send(&Message::Result(ordering))?;
+
+        if settings.hints
+            && let Some(hint) = Hint::for_any(game.secret(), game.guesses())
+        {
+            connection.send(&Message::Hint(hint.to_string()))?;
+        }
+    }
+
+    let won = game.state() == State::Won;
+    connection.send(&Message::Over {
+        won,
+        secret: game.secret(),
+    })?;
+    let key = if won {
+        "host_they_won"
+    } else {
+        "host_they_lost"
+    };
+    writeln!(output, "{}", tr(key))?;
+
+    Ok(game.state())
+}
+
+/// Runs the guest's side: reads guesses from `input` and shows the host's answers.
+pub fn join<R, W>(connection: &mut Connection, input: &mut R, output: &mut W) -> io::Result<State>
+where
+    R: BufRead,
+    W: Write,
+{
+    loop {
+        match connection.receive()? {
+            Message::Hello { min, max, lives } => writeln!(
+                output,
+                "{}",
+                tr_with(
+                    "join_hello",
+                    &[("min", &min), ("max", &max), ("lives", &lives)]
+                )
+            )?,
+            Message::Turn { lives } => {
+                let question = format!("{}: ", tr_with("prompt", &[("lives", &lives)]));
+                let guess = prompt(input, output, &question)?;
+                connection.send(&Message::Guess(guess))?;
+            }
+            Message::Result(Ordering::Less) => writeln!(output, "{}", tr("too_small"))?,
+            Message::Result(Ordering::Greater) => writeln!(output, "{}", tr("too_big"))?,
+            Message::Result(Ordering::Equal) => {}
+            // The host explains the problem in its own language; only the rest is ours.
+            Message::Invalid(text) => writeln!(output, "{}", not_counted(&text))?,
+            Message::Hint(text) => writeln!(output, "{text}")?,
+            Message::Over { won: true, .. } => {
+                writeln!(output, "{}", tr("you_win"))?;
+                return Ok(State::Won);
+            }
+            Message::Over { won: false, secret } => {
+                writeln!(
+                    output,
+                    "{} {}",
+                    tr("you_lose"),
+                    tr_with("secret_number_was", &[("secret", &secret)])
+                )?;
+                return Ok(State::Lost);
+            }
+            Message::Guess(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the host sent a guess, but guessing is our job",
+                ));
+            }
+        }
+    }
+}
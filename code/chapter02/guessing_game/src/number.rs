@@ -0,0 +1,179 @@
+/*
+ * The kinds of numbers a secret can be.
+ *
+ * The game only needs a handful of things from a number: parse it, compare it,
+ * pick a random one and say how far apart two are. The `Number` trait lists them,
+ * and `Game<N: Number>` works for any type that implements it:
+ * - u32, whole numbers from 0 up (the classic game)
+ * - i64, whole numbers that can be negative, like -50..=50
+ * - f64, decimals, where a guess close enough (the tolerance) counts as correct
+ */
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+use rand::distr::uniform::SampleUniform;
+
+/// Why text could not be read as a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    NotANumber,
+    /// Negative, but this kind of number can't be.
+    Negative,
+    /// A number, but too big (or too small) for this kind.
+    TooBig,
+}
+
+/*
+ * The supertraits after the colon are what every Number must also be:
+ * Copy so numbers can be passed around freely, PartialOrd to compare them,
+ * From<u32> to turn the presets' u32 values into any kind of number,
+ * SampleUniform so rand can pick one from a range,
+ * and Send + Sync + 'static so errors holding a number can travel inside an io::Error.
+ */
+/// A type the secret and the guesses can be.
+pub trait Number:
+    Copy + PartialOrd + fmt::Display + fmt::Debug + From<u32> + SampleUniform + Send + Sync + 'static
+{
+    /// Which `--numbers` choice plays with this type.
+    const KIND: NumberKind;
+
+    /// How close a guess has to be, unless `--tolerance` says otherwise.
+    const DEFAULT_TOLERANCE: Self;
+
+    /// Reads typed text, which is already trimmed and not empty.
+    fn parse(text: &str) -> Result<Self, ParseError>;
+
+    /// The number as an f64, for measuring distances.
+    fn to_f64(self) -> f64;
+
+    /// Picks a number in `min..=max`.
+    fn random<R: Rng + ?Sized>(rng: &mut R, min: Self, max: Self) -> Self {
+        rng.random_range(min..=max)
+    }
+
+    /// How far apart two numbers are.
+    fn distance(self, other: Self) -> f64 {
+        (self.to_f64() - other.to_f64()).abs()
+    }
+
+    /// True when the two numbers are at most `tolerance` apart.
+    fn within(self, other: Self, tolerance: Self) -> bool;
+}
+
+/// Parses whole numbers into the much bigger i128 first, so "-5" and "99999999999"
+/// are still read as numbers and get a precise error,
+/// instead of the generic "not a number" that parsing straight into u32 would give.
+fn parse_whole(text: &str) -> Result<i128, ParseError> {
+    text.parse().map_err(|_| ParseError::NotANumber)
+}
+
+impl Number for u32 {
+    const KIND: NumberKind = NumberKind::Whole;
+    const DEFAULT_TOLERANCE: Self = 0;
+
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        let number = parse_whole(text)?;
+        if number < 0 {
+            return Err(ParseError::Negative);
+        }
+        u32::try_from(number).map_err(|_| ParseError::TooBig)
+    }
+
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn within(self, other: Self, tolerance: Self) -> bool {
+        self.abs_diff(other) <= tolerance
+    }
+}
+
+impl Number for i64 {
+    const KIND: NumberKind = NumberKind::Signed;
+    const DEFAULT_TOLERANCE: Self = 0;
+
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        i64::try_from(parse_whole(text)?).map_err(|_| ParseError::TooBig)
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    /*
+     * An f64 only has 53 bits for the digits, so above 2^53 neighbouring i64 values
+     * become the same f64, and i64::MAX - 1 would look like i64::MAX.
+     * abs_diff stays exact: it returns a u64, which fits even i64::MIN to i64::MAX.
+     */
+    fn within(self, other: Self, tolerance: Self) -> bool {
+        // Settings::validate rejects a negative tolerance; here it would let nothing win.
+        u64::try_from(tolerance).is_ok_and(|tolerance| self.abs_diff(other) <= tolerance)
+    }
+}
+
+impl Number for f64 {
+    const KIND: NumberKind = NumberKind::Decimal;
+    // Half a unit either way, so the default range still has about 100 possible answers.
+    const DEFAULT_TOLERANCE: Self = 0.5;
+
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        let number: f64 = text.parse().map_err(|_| ParseError::NotANumber)?;
+        // "inf" and "NaN" parse as f64, but nobody can guess them.
+        if !number.is_finite() {
+            return Err(ParseError::NotANumber);
+        }
+        Ok(number)
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn within(self, other: Self, tolerance: Self) -> bool {
+        self.distance(other) <= tolerance
+    }
+
+    /// Decimal secrets have two decimal places, so they can be read out in full.
+    fn random<R: Rng + ?Sized>(rng: &mut R, min: Self, max: Self) -> Self {
+        let number: f64 = rng.random_range(min..=max);
+        ((number * 100.0).round() / 100.0).clamp(min, max)
+    }
+}
+
+/// Which `Number` type to play with, chosen with `--numbers`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    /// u32
+    #[default]
+    Whole,
+    /// i64
+    Signed,
+    /// f64
+    Decimal,
+}
+
+impl fmt::Display for NumberKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NumberKind::Whole => "whole",
+            NumberKind::Signed => "signed",
+            NumberKind::Decimal => "decimal",
+        })
+    }
+}
+
+impl FromStr for NumberKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "whole" => Ok(NumberKind::Whole),
+            "signed" => Ok(NumberKind::Signed),
+            "decimal" => Ok(NumberKind::Decimal),
+            other => Err(format!(
+                "unknown kind of numbers '{other}' (expected whole, signed or decimal)"
+            )),
+        }
+    }
+}
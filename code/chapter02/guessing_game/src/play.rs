@@ -0,0 +1,295 @@
+/*
+ * BufRead: a reader with an internal buffer, which gives us read_line.
+ * Write: anything we can write bytes to, like stdout or a Vec<u8>.
+ * Taking these traits instead of io::stdin()/println! lets tests
+ * play the game with scripted input and inspect the output.
+ */
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+use crate::color::{Color, paint};
+use crate::difficulty::{Difficulty, Settings};
+use crate::game::{Game, State};
+use crate::hints::Hint;
+use crate::i18n::{is_listed, tr, tr_with};
+use crate::number::Number;
+
+/*
+ * A trait lists the methods a type must have to be used somewhere.
+ * `play` only needs these, so the number game and the code-breaking game
+ * share the same loop: lives, the clock, giving up, winning and losing.
+ */
+/// A game the play loop can run: it judges typed guesses until it is won or lost.
+pub trait Puzzle {
+    fn state(&self) -> State;
+    fn lives(&self) -> u32;
+    /// How long the whole game may take, if there is a limit.
+    fn time_limit(&self) -> Option<Duration>;
+    /// How long each guess may take, if there is a limit.
+    fn turn_limit(&self) -> Option<Duration>;
+    /// The time for a guess ran out, which costs a life.
+    fn miss(&mut self);
+    /// Ends the game as a loss.
+    fn forfeit(&mut self);
+    /// Ends the game without a result, because the player left.
+    fn abandon(&mut self);
+    /// A sentence revealing the secret to a player who lost.
+    fn reveal(&self) -> String;
+    /// Judges one typed line: explains why it was rejected, or how close the guess was.
+    fn answer<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<()>;
+}
+
+/// Plays `game` until it is won or lost, reading guesses from `input`.
+pub fn play<P, R, W>(game: &mut P, input: &mut R, output: &mut W) -> io::Result<State>
+where
+    P: Puzzle,
+    R: BufRead,
+    W: Write,
+{
+    let started = Instant::now();
+
+    while !announce_end(game, output)? {
+        writeln!(
+            output,
+            "{}.",
+            tr_with("prompt", &[("lives", &game.lives())])
+        )?;
+
+        /*
+         * Create a mutable, empty String to store user input.
+         * String::new() is an associated function.
+         */
+        let mut guess = String::new();
+
+        /*
+         * read_line gives back 0 bytes only at the end of the input (EOF, or Ctrl-D in a terminal).
+         * Nobody is left to play, so the game is abandoned rather than lost:
+         * it isn't saved as a score and doesn't make the adaptive level easier.
+         */
+        if input.read_line(&mut guess)? == 0 {
+            writeln!(output, "\n{}", tr("no_more_input"))?;
+            game.abandon();
+            continue;
+        }
+
+        // read_line can't time out, so here the clock is only checked after every answer.
+        if let Some(limit) = game.time_limit()
+            && started.elapsed() > limit
+        {
+            writeln!(output, "{}", tr("times_up"))?;
+            game.forfeit();
+            continue;
+        }
+
+        game.answer(&guess, output)?;
+    }
+
+    Ok(game.state())
+}
+
+/// Prints the win or lose message if the game is over. Returns whether it was.
+pub(crate) fn announce_end<P: Puzzle, W: Write>(game: &P, output: &mut W) -> io::Result<bool> {
+    match game.state() {
+        State::Won => writeln!(output, "{}", paint(&tr("you_win"), Color::Green))?,
+        State::Lost => writeln!(
+            output,
+            "{} {}",
+            paint(&tr("you_lose"), Color::Red),
+            game.reveal()
+        )?,
+        State::Abandoned => writeln!(output, "{}", game.reveal())?,
+        State::Playing => return Ok(false),
+    }
+    Ok(true)
+}
+
+/*
+ * Game already has state, lives and forfeit methods of its own.
+ * Inside this impl, `Game::state(self)` calls those, not the trait methods we are defining.
+ */
+impl<N: Number> Puzzle for Game<N> {
+    fn state(&self) -> State {
+        Game::state(self)
+    }
+
+    fn lives(&self) -> u32 {
+        Game::lives(self)
+    }
+
+    fn time_limit(&self) -> Option<Duration> {
+        self.settings().time_limit
+    }
+
+    fn turn_limit(&self) -> Option<Duration> {
+        self.settings().turn_limit
+    }
+
+    fn miss(&mut self) {
+        Game::miss(self)
+    }
+
+    fn forfeit(&mut self) {
+        Game::forfeit(self)
+    }
+
+    fn abandon(&mut self) {
+        Game::abandon(self)
+    }
+
+    fn reveal(&self) -> String {
+        tr_with("secret_number_was", &[("secret", &self.secret())])
+    }
+
+    fn answer<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<()> {
+        let guess = match self.parse_guess(line) {
+            Ok(num) => num,
+            Err(error) => return writeln!(output, "{}", not_counted(&error)),
+        };
+
+        writeln!(output, "{}", tr_with("you_guessed", &[("guess", &guess)]))?;
+
+        match self.guess(guess) {
+            Ok(Ordering::Less) => writeln!(output, "{}", paint(&tr("too_small"), Color::Cyan))?,
+            Ok(Ordering::Greater) => writeln!(output, "{}", paint(&tr("too_big"), Color::Yellow))?,
+            Ok(Ordering::Equal) => {}
+            Err(error) => return Err(io::Error::other(error)),
+        }
+
+        // A decimal guess within the tolerance wins without being exact: no hint needed then.
+        if self.settings().hints
+            && self.state() != State::Won
+            && let Some(hint) = Hint::for_any(self.secret(), self.guesses())
+        {
+            writeln!(output, "{hint}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Explains a rejected guess, and that it was free.
+pub(crate) fn not_counted(error: &dyn std::fmt::Display) -> String {
+    tr_with("not_counted", &[("error", error)])
+}
+
+/// Asks for a difficulty. An empty answer picks `Normal`.
+pub fn choose_settings<R, W>(input: &mut R, output: &mut W) -> io::Result<Settings>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(output, "{}", tr("menu_title"))?;
+    for (number, level) in Difficulty::ALL.iter().enumerate() {
+        if *level == Difficulty::Custom {
+            writeln!(output, "  {}) {}", number + 1, tr("menu_custom"))?;
+        } else {
+            writeln!(output, "  {}) {}", number + 1, level.settings())?;
+        }
+    }
+
+    let difficulty = loop {
+        let answer = prompt(input, output, &tr("menu_difficulty"))?;
+        if answer.is_empty() {
+            break Difficulty::Normal;
+        }
+        match answer.parse() {
+            Ok(level) => break level,
+            Err(message) => writeln!(output, "{message}")?,
+        }
+    };
+
+    let mut settings = difficulty.settings();
+    if difficulty == Difficulty::Custom {
+        loop {
+            settings.min = prompt_number(input, output, &tr("menu_smallest"), settings.min)?;
+            settings.max = prompt_number(input, output, &tr("menu_largest"), settings.max)?;
+            settings.lives = prompt_number(input, output, &tr("menu_lives"), settings.lives)?;
+            let secs = prompt_number(input, output, &tr("menu_time_limit"), 0)?;
+            settings.time_limit = (secs > 0).then(|| Duration::from_secs(secs));
+            let secs = prompt_number(input, output, &tr("menu_turn_limit"), 0)?;
+            settings.turn_limit = (secs > 0).then(|| Duration::from_secs(secs));
+
+            match settings.validate() {
+                Ok(()) => break,
+                Err(message) => writeln!(
+                    output,
+                    "{}",
+                    tr_with("menu_try_again", &[("message", &message)])
+                )?,
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Prints `question` and returns the trimmed answer.
+///
+/// Running out of input (EOF) is an `UnexpectedEof` error, since nobody is left to answer.
+pub fn prompt<R, W>(input: &mut R, output: &mut W, question: &str) -> io::Result<String>
+where
+    R: BufRead,
+    W: Write,
+{
+    write!(output, "{question}")?;
+    // write! does not end the line, so we flush to make the question appear before we read.
+    output.flush()?;
+
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no more input to answer with",
+        ));
+    }
+    Ok(answer.trim().to_string())
+}
+
+/// Asks whether to play another round. Running out of input means no.
+pub fn play_again<R, W>(input: &mut R, output: &mut W) -> io::Result<bool>
+where
+    R: BufRead,
+    W: Write,
+{
+    loop {
+        let answer = match prompt(input, output, &tr("play_again")) {
+            Ok(answer) => answer,
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(error) => return Err(error),
+        };
+
+        // The accepted answers are listed in the messages, like "j, ja" in German.
+        let answer = answer.to_lowercase();
+        if is_listed("play_again_yes", &answer) {
+            return Ok(true);
+        }
+        if answer.is_empty() || is_listed("play_again_no", &answer) {
+            return Ok(false);
+        }
+        writeln!(output, "{}", tr("play_again_help"))?;
+    }
+}
+
+fn prompt_number<R, W, T>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+    default: T,
+) -> io::Result<T>
+where
+    R: BufRead,
+    W: Write,
+    T: std::str::FromStr + std::fmt::Display + Copy,
+{
+    loop {
+        let answer = prompt(input, output, &format!("{question} [{default}]: "))?;
+        if answer.is_empty() {
+            return Ok(default);
+        }
+        match answer.parse() {
+            Ok(number) => return Ok(number),
+            Err(_) => writeln!(output, "{}", tr("menu_whole_number"))?,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * Reverse mode: the player thinks of a number and the computer guesses it.
+ * The player answers with the same Ordering the game normally gives,
+ * just with the roles swapped: "higher" means the guess was too small (Ordering::Less).
+ */
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+
+use crate::difficulty::Settings;
+use crate::i18n::{is_listed, tr, tr_with};
+use crate::play::prompt;
+use crate::solver::Solver;
+
+/// How a reverse game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The computer found the number with this many guesses.
+    Found { guesses: u32 },
+    /// The computer ran out of lives, so the player wins.
+    OutOfLives,
+    /// The answers contradict each other: no number fits all of them.
+    Cheated,
+}
+
+/// Reads the player's answer about `guess` as how the guess compares with their number.
+///
+/// The accepted words are listed in the messages, like "h, higher, too small" in English.
+pub fn parse_answer(answer: &str) -> Option<Ordering> {
+    let answer = answer.trim().to_lowercase();
+    // Their number is higher, so our guess is too small.
+    if is_listed("reverse_higher", &answer) {
+        Some(Ordering::Less)
+    } else if is_listed("reverse_lower", &answer) {
+        Some(Ordering::Greater)
+    } else if is_listed("reverse_correct", &answer) {
+        Some(Ordering::Equal)
+    } else {
+        None
+    }
+}
+
+/// The computer guesses the player's number, asking after each guess.
+pub fn play<R, W>(settings: &Settings, input: &mut R, output: &mut W) -> io::Result<Outcome>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(
+        output,
+        "{}",
+        tr_with(
+            "reverse_intro",
+            &[
+                ("min", &settings.min),
+                ("max", &settings.max),
+                ("lives", &settings.lives)
+            ]
+        )
+    )?;
+
+    let mut solver = Solver::new(settings.min, settings.max);
+    let mut lives = settings.lives;
+    let mut guesses = 0;
+    // The biggest guess they called too small and the smallest they called too big.
+    let mut above: Option<u32> = None;
+    let mut below: Option<u32> = None;
+
+    while lives > 0 {
+        let Some(guess) = solver.next_guess() else {
+            break;
+        };
+        guesses += 1;
+
+        let ordering = loop {
+            let question = tr_with("reverse_question", &[("guess", &guess)]);
+            match parse_answer(&prompt(input, output, &question)?) {
+                Some(ordering) => break ordering,
+                None => writeln!(output, "{}", tr("reverse_help"))?,
+            }
+        };
+
+        match ordering {
+            Ordering::Equal => {
+                writeln!(
+                    output,
+                    "{}",
+                    tr_with("reverse_found", &[("guess", &guess), ("guesses", &guesses)])
+                )?;
+                return Ok(Outcome::Found { guesses });
+            }
+            Ordering::Less => above = above.max(Some(guess)),
+            Ordering::Greater => below = Some(below.map_or(guess, |b| b.min(guess))),
+        }
+
+        solver.learn(guess, ordering);
+        lives -= 1;
+    }
+
+    if solver.remaining().is_some() {
+        writeln!(output, "{}", tr("reverse_out_of_lives"))?;
+        return Ok(Outcome::OutOfLives);
+    }
+
+    let reason = match (above, below) {
+        (Some(above), Some(below)) => {
+            tr_with("reverse_between", &[("above", &above), ("below", &below)])
+        }
+        (Some(above), None) => tr_with(
+            "reverse_above",
+            &[("above", &above), ("max", &settings.max)],
+        ),
+        (None, Some(below)) => tr_with(
+            "reverse_below",
+            &[("below", &below), ("min", &settings.min)],
+        ),
+        (None, None) => tr("reverse_impossible"),
+    };
+    writeln!(
+        output,
+        "{}",
+        tr_with("reverse_cheated", &[("reason", &reason)])
+    )?;
+    Ok(Outcome::Cheated)
+}
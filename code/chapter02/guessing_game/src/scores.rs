@@ -0,0 +1,237 @@
+/*
+ * The score file is plain text with one finished game per line,
+ * the fields separated by tabs:
+ *
+ *     name<TAB>difficulty<TAB>won|lost<TAB>guesses<TAB>YYYY-MM-DD
+ *
+ * Lines starting with '#' are ignored, so the file can be annotated by hand.
+ */
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, State};
+use crate::i18n::tr;
+
+/// `name` as the score file stores it.
+///
+/// A tab or newline inside the name would break the file format, so control
+/// characters become spaces. Compare a name with saved ones only after this.
+pub fn clean_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// One finished game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub name: String,
+    pub difficulty: Difficulty,
+    pub won: bool,
+    pub guesses: u32,
+    /// The day the game was played, as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+impl Score {
+    /// The score for a finished `game`, dated today.
+    pub fn from_game(name: &str, game: &Game) -> Self {
+        Self {
+            name: name.to_string(),
+            difficulty: game.settings().difficulty,
+            won: game.state() == State::Won,
+            guesses: game.guesses().len() as u32,
+            date: today(),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        let name = clean_name(&self.name);
+        let result = if self.won { "won" } else { "lost" };
+
+        format!(
+            "{name}\t{}\t{result}\t{}\t{}",
+            self.difficulty, self.guesses, self.date
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, difficulty, result, guesses, date] = fields[..] else {
+            return Err(format!("expected 5 fields, found {}", fields.len()));
+        };
+
+        let won = match result {
+            "won" => true,
+            "lost" => false,
+            other => return Err(format!("unknown result '{other}'")),
+        };
+        let guesses = guesses
+            .parse()
+            .map_err(|_| format!("'{guesses}' is not a number of guesses"))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            difficulty: difficulty.parse()?,
+            won,
+            guesses,
+            date: date.to_string(),
+        })
+    }
+}
+
+/// Where scores are kept when `--scores-file` is not given.
+///
+/// This follows each platform's convention for per-user application data:
+/// `$XDG_DATA_HOME` or `~/.local/share` on Linux, `~/Library/Application Support`
+/// on macOS and `%APPDATA%` on Windows. `None` if no home directory is known.
+pub fn default_path() -> Option<PathBuf> {
+    let data_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    data_dir.map(|dir| dir.join("guessing_game").join("scores.tsv"))
+}
+
+/// Reads every score in `path`. A file that does not exist yet holds no scores.
+pub fn load(path: &Path) -> io::Result<Vec<Score>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut scores = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let score = Score::from_line(&line).map_err(|message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: {message}", path.display(), number + 1),
+            )
+        })?;
+        scores.push(score);
+    }
+
+    Ok(scores)
+}
+
+/// Adds `score` to the end of `path`, creating the file and its folders if needed.
+pub fn append(path: &Path, score: &Score) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", score.to_line())
+}
+
+/// Sorts `scores` best first: wins before losses, then fewest guesses, then oldest.
+pub fn rank(scores: &mut [Score]) {
+    scores.sort_by(|a, b| {
+        b.won
+            .cmp(&a.won)
+            .then(a.guesses.cmp(&b.guesses))
+            .then_with(|| a.date.cmp(&b.date))
+    });
+}
+
+/// Prints `scores` as a table, in the order given.
+pub fn write_leaderboard<W: Write>(output: &mut W, scores: &[Score]) -> io::Result<()> {
+    if scores.is_empty() {
+        return writeln!(output, "{}", tr("leaderboard_empty"));
+    }
+
+    let (player, difficulty, result, guesses) = (
+        tr("leaderboard_player"),
+        tr("leaderboard_difficulty"),
+        tr("leaderboard_result"),
+        tr("leaderboard_guesses"),
+    );
+    let (won, lost) = (tr("leaderboard_won"), tr("leaderboard_lost"));
+    // Each column is as wide as its heading or its widest value, in any language.
+    let name_width = scores
+        .iter()
+        .map(|score| score.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(player.chars().count());
+    let difficulty_width = difficulty.chars().count().max("normal".len());
+    let result_width = [&result, &won, &lost]
+        .iter()
+        .map(|text| text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let guesses_width = guesses.chars().count();
+
+    writeln!(
+        output,
+        "{:>4}  {player:<name_width$}  {difficulty:<difficulty_width$}  {result:<result_width$}  {guesses:>guesses_width$}  {}",
+        "#",
+        tr("leaderboard_date")
+    )?;
+    for (rank, score) in scores.iter().enumerate() {
+        writeln!(
+            output,
+            "{:>4}  {:<name_width$}  {:<difficulty_width$}  {:<result_width$}  {:>guesses_width$}  {}",
+            rank + 1,
+            score.name,
+            score.difficulty.to_string(),
+            if score.won { &won } else { &lost },
+            score.guesses,
+            score.date
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Today's date in UTC as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/*
+ * Turns a count of days since 1970-01-01 into a (year, month, day) calendar date.
+ * The standard library has no calendar, so this is Howard Hinnant's
+ * `civil_from_days` algorithm, which works in 400-year "eras".
+ */
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
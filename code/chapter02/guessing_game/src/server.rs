@@ -0,0 +1,366 @@
+/*
+ * The game in a browser: `guessing_game serve` is a tiny HTTP server on localhost.
+ *
+ * HTTP is text over TCP, like the protocol in net.rs, just with more rules.
+ * A request is a line like `POST /api/games HTTP/1.1`, some `Name: value` headers,
+ * an empty line and an optional body; the response looks the same with a status instead.
+ * We only need a small part of it, so it is written by hand with std::net:
+ *
+ *     GET  /                          the HTML page (web/index.html)
+ *     POST /api/games                 start a new game, answers its state with an id
+ *     GET  /api/games/<id>            the state of a game
+ *     POST /api/games/<id>/guesses    the body is the guess as text, like `50`
+ *
+ * Every /api answer is JSON, so the endpoints can be tried with a local client:
+ *
+ *     curl -X POST http://127.0.0.1:8080/api/games
+ *     curl -d 50 http://127.0.0.1:8080/api/games/1/guesses
+ *
+ * Every connection gets its own thread, so a browser that opens a connection and sends
+ * nothing yet can't hold up the others. The games are shared behind a Mutex.
+ */
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::difficulty::Settings;
+use crate::game::{Game, State};
+use crate::hints::Hint;
+use crate::transcript::{json_string, result_name};
+
+/// Where `serve` listens when no address is given. Only this computer can connect.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+/// The page the browser loads, copied into the program like the messages in i18n.rs.
+pub const PAGE: &str = include_str!("../web/index.html");
+
+/// The biggest request body accepted. A guess is a few bytes.
+const MAX_BODY: usize = 1024;
+
+/// The longest request line or header accepted, in bytes.
+const MAX_LINE: usize = 8 * 1024;
+
+/// The most headers accepted in one request.
+const MAX_HEADERS: usize = 100;
+
+/// The most games kept. Starting one more forgets the oldest, finished ones first.
+pub const MAX_GAMES: usize = 100;
+
+/// How long a client may take to send its request before we give up on it.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The parts of an HTTP request we use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// The path without any `?query`.
+    pub path: String,
+    pub body: String,
+}
+
+impl Request {
+    pub fn new(method: &str, path: &str, body: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    /// Reads one request: the request line, the headers and a body of `Content-Length` bytes.
+    pub fn read<R: BufRead>(reader: &mut R) -> io::Result<Self> {
+        let bad = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+        let mut line = String::new();
+        read_line(reader, &mut line)?;
+        let mut words = line.split_whitespace();
+        let (Some(method), Some(target)) = (words.next(), words.next()) else {
+            return Err(bad("not an HTTP request"));
+        };
+        let path = target.split('?').next().unwrap_or(target);
+
+        let mut length = 0;
+        for headers in 0.. {
+            if headers > MAX_HEADERS {
+                return Err(bad("too many headers"));
+            }
+            let mut header = String::new();
+            if read_line(reader, &mut header)? == 0 {
+                return Err(bad("the request ended inside the headers"));
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            // Header names are case-insensitive: content-length and Content-Length are the same.
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| bad("bad Content-Length"))?;
+            }
+        }
+        if length > MAX_BODY {
+            return Err(bad("the request body is too big"));
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Self::new(method, path, &String::from_utf8_lossy(&body)))
+    }
+}
+
+/// `read_line` that gives up after `MAX_LINE` bytes, so a client can't fill our memory
+/// with one endless line.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> io::Result<usize> {
+    let read = reader.by_ref().take(MAX_LINE as u64).read_line(line)?;
+    if read == MAX_LINE && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "a request line is too long",
+        ));
+    }
+    Ok(read)
+}
+
+/// What we answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    /// A JSON `{"error": ...}` answer.
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, format!("{{\"error\": {}}}", json_string(message)))
+    }
+
+    pub fn html(body: &str) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body: body.to_string(),
+        }
+    }
+
+    /// Writes the response. `Connection: close` tells the client not to send another request.
+    pub fn write<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        write!(
+            output,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        output.flush()
+    }
+}
+
+/// The standard words that go with each status code we use.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Entity",
+        _ => "Unknown",
+    }
+}
+
+/// Every game started in the browser, all with the same settings.
+pub struct Server<G: Rng> {
+    settings: Settings,
+    rng: G,
+    /// The games by id. Ids only grow, so the first entries are the oldest.
+    games: BTreeMap<usize, Game>,
+    next_id: usize,
+}
+
+impl<G: Rng> Server<G> {
+    pub fn new(settings: Settings, rng: G) -> Self {
+        Self {
+            settings,
+            rng,
+            games: BTreeMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Answers one request. Nothing here touches the network, so tests can call it directly.
+    pub fn handle(&mut self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+        match (request.method.as_str(), &segments[..]) {
+            ("GET", [""]) => Response::html(PAGE),
+            ("POST", ["api", "games"]) => {
+                self.make_room();
+                let id = self.next_id;
+                self.next_id += 1;
+                let game = Game::random(self.settings.clone(), &mut self.rng);
+                let response = Response::json(201, state_json(id, &game));
+                self.games.insert(id, game);
+                response
+            }
+            ("GET", ["api", "games", id]) => match self.game(id) {
+                Some((id, game)) => Response::json(200, state_json(id, game)),
+                None => Response::error(404, "no game with that id"),
+            },
+            ("POST", ["api", "games", id, "guesses"]) => match self.game(id) {
+                Some((id, game)) => guess(id, game, &request.body),
+                None => Response::error(404, "no game with that id"),
+            },
+            (_, [""] | ["api", "games"] | ["api", "games", _] | ["api", "games", _, "guesses"]) => {
+                Response::error(405, "that method is not allowed here")
+            }
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    /// Forgets a game when `MAX_GAMES` are kept: the oldest finished one,
+    /// or the oldest of all when every game is still being played.
+    fn make_room(&mut self) {
+        if self.games.len() < MAX_GAMES {
+            return;
+        }
+        let oldest = self
+            .games
+            .iter()
+            .find(|(_, game)| game.is_over())
+            .or_else(|| self.games.iter().next())
+            .map(|(&id, _)| id);
+        if let Some(id) = oldest {
+            self.games.remove(&id);
+        }
+    }
+
+    /// The game with `id`, after checking its clock.
+    fn game(&mut self, id: &str) -> Option<(usize, &mut Game)> {
+        let id: usize = id.parse().ok()?;
+        let game = self.games.get_mut(&id)?;
+        // Nothing ticks between requests, so the game clock is checked whenever a game is used.
+        if let Some(limit) = game.settings().time_limit
+            && game.elapsed() >= limit
+        {
+            game.forfeit();
+        }
+        Some((id, game))
+    }
+
+    /// Answers connections until the listener fails, each one on a thread of its own.
+    ///
+    /// A client that hangs up, sends garbage or sends nothing only loses its own connection.
+    pub fn serve(self, listener: &TcpListener) -> io::Result<()>
+    where
+        G: Send + 'static,
+    {
+        let server = Arc::new(Mutex::new(self));
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                if let Err(error) = answer(&server, stream) {
+                    eprintln!("warning: a request failed: {error}");
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Reads one request from `stream` and answers it.
+///
+/// The lock is only held while the request is handled, not while a slow client
+/// is still sending it.
+pub fn answer<G: Rng>(server: &Mutex<Server<G>>, stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let response = match Request::read(&mut reader) {
+        Ok(request) => server
+            .lock()
+            .map_err(|_| io::Error::other("another request crashed the server"))?
+            .handle(&request),
+        Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+            Response::error(400, &error.to_string())
+        }
+        Err(error) => return Err(error),
+    };
+    response.write(&mut writer)
+}
+
+/// Plays `text` as the next guess in `game`.
+fn guess(id: usize, game: &mut Game, text: &str) -> Response {
+    match game.parse_guess(text).and_then(|guess| game.guess(guess)) {
+        Ok(_) => Response::json(200, state_json(id, game)),
+        // The guess was rejected without costing a life: say why, and show the unchanged game.
+        Err(error) => Response::json(
+            422,
+            format!(
+                "{{\"error\": {}, \"game\": {}}}",
+                json_string(&error.to_string()),
+                state_json(id, game)
+            ),
+        ),
+    }
+}
+
+/// The game as the page sees it. The secret is only included once the game is over.
+pub fn state_json(id: usize, game: &Game) -> String {
+    let settings = game.settings();
+    let state = match game.state() {
+        State::Playing => "playing",
+        State::Won => "won",
+        State::Lost => "lost",
+        State::Abandoned => "abandoned",
+    };
+    let guesses: Vec<String> = game.guesses().iter().map(u32::to_string).collect();
+
+    let mut json = format!(
+        "{{\"id\": {id}, \"state\": \"{state}\", \"min\": {}, \"max\": {}, \"lives\": {}, \"guesses\": [{}]",
+        settings.min,
+        settings.max,
+        game.lives(),
+        guesses.join(", ")
+    );
+    if let Some(turn) = game.turns().last()
+        && let (Some(guess), Some(ordering)) = (turn.guess, turn.ordering)
+    {
+        json.push_str(&format!(
+            ", \"last\": {{\"guess\": {guess}, \"result\": \"{}\"}}",
+            result_name(ordering)
+        ));
+    }
+    if settings.hints
+        && let Some(hint) = Hint::for_any(game.secret(), game.guesses())
+    {
+        json.push_str(&format!(", \"hint\": {}", json_string(&hint.to_string())));
+    }
+    if game.is_over() {
+        json.push_str(&format!(", \"secret\": {}", game.secret()));
+    }
+    json.push('}');
+    json
+}
@@ -0,0 +1,238 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use rand::Rng;
+
+use crate::difficulty::Settings;
+use crate::game::{Game, State};
+use crate::i18n::{tr, tr_with};
+
+/*
+ * Binary search: always guess the middle of the numbers that are still possible.
+ * Every "Too small!" or "Too big!" throws away half of them,
+ * so 1..=100 never takes more than 7 guesses (2^7 = 128 >= 100).
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solver {
+    /// The smallest and largest numbers the secret can still be, or `None` once nothing fits.
+    remaining: Option<(u32, u32)>,
+}
+
+impl Solver {
+    pub fn new(min: u32, max: u32) -> Self {
+        Self {
+            remaining: (min <= max).then_some((min, max)),
+        }
+    }
+
+    /// The middle of the remaining interval, or `None` if the answers ruled out every number.
+    pub fn next_guess(&self) -> Option<u32> {
+        // low + (high - low) / 2 instead of (low + high) / 2, which could overflow.
+        self.remaining.map(|(low, high)| low + (high - low) / 2)
+    }
+
+    /// Narrows the interval with how `guess` compared to the secret.
+    pub fn learn(&mut self, guess: u32, ordering: Ordering) {
+        let Some((low, high)) = self.remaining else {
+            return;
+        };
+
+        // checked_add/checked_sub give None instead of going past 0 or u32::MAX.
+        self.remaining = match ordering {
+            // The guess was too small, so the secret is above it.
+            Ordering::Less => guess.checked_add(1).map(|above| (low.max(above), high)),
+            // The guess was too big, so the secret is below it.
+            Ordering::Greater => guess.checked_sub(1).map(|below| (low, high.min(below))),
+            Ordering::Equal => Some((low.max(guess), high.min(guess))),
+        }
+        .filter(|(low, high)| low <= high);
+    }
+
+    /// The numbers the secret can still be, as `(low, high)`.
+    pub fn remaining(&self) -> Option<(u32, u32)> {
+        self.remaining
+    }
+}
+
+/// Lets the solver play `game` to the end, printing every guess.
+pub fn auto_play<W: Write>(game: &mut Game, output: &mut W) -> io::Result<State> {
+    let mut solver = Solver::new(game.settings().min, game.settings().max);
+
+    while !game.is_over() {
+        let Some(guess) = solver.next_guess() else {
+            break;
+        };
+        let ordering = game.guess(guess).map_err(io::Error::other)?;
+
+        let reply = match ordering {
+            Ordering::Less => tr("too_small"),
+            Ordering::Greater => tr("too_big"),
+            Ordering::Equal => tr("bot_correct"),
+        };
+        writeln!(
+            output,
+            "{}",
+            tr_with(
+                "bot_guessed",
+                &[
+                    ("guess", &guess),
+                    ("lives", &game.lives()),
+                    ("reply", &reply)
+                ]
+            )
+        )?;
+        solver.learn(guess, ordering);
+    }
+
+    let guesses = game.guesses().len();
+    match game.state() {
+        State::Won => writeln!(output, "{}", tr_with("bot_won", &[("guesses", &guesses)]))?,
+        _ => writeln!(
+            output,
+            "{} {}",
+            tr_with("bot_lost", &[("guesses", &guesses)]),
+            tr_with("secret_number_was", &[("secret", &game.secret())])
+        )?,
+    }
+
+    Ok(game.state())
+}
+
+/// How many guesses the solver needs to find `secret`, ignoring lives.
+pub fn guesses_needed(min: u32, max: u32, secret: u32) -> u32 {
+    let mut solver = Solver::new(min, max);
+    let mut guesses = 0;
+
+    while let Some(guess) = solver.next_guess() {
+        guesses += 1;
+        let ordering = guess.cmp(&secret);
+        if ordering == Ordering::Equal {
+            break;
+        }
+        solver.learn(guess, ordering);
+    }
+
+    guesses
+}
+
+/// How many guesses the solver needed over many games, compared with the lives budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Benchmark {
+    pub settings: Settings,
+    /// Number of guesses needed -> number of games that needed exactly that many.
+    pub counts: BTreeMap<u32, u32>,
+}
+
+impl Benchmark {
+    /// Solves `games` random secrets from the settings' range.
+    pub fn run<R: Rng + ?Sized>(settings: Settings, games: u32, rng: &mut R) -> Self {
+        let mut counts = BTreeMap::new();
+        for _ in 0..games {
+            let secret = rng.random_range(settings.min..=settings.max);
+            let needed = guesses_needed(settings.min, settings.max, secret);
+            *counts.entry(needed).or_insert(0) += 1;
+        }
+
+        Self { settings, counts }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Games won within the lives budget. The last life can still win, so `lives` guesses are allowed.
+    pub fn wins(&self) -> u32 {
+        self.counts
+            .range(..=self.settings.lives)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    pub fn worst(&self) -> u32 {
+        self.counts.keys().next_back().copied().unwrap_or(0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        let total: u64 = self
+            .counts
+            .iter()
+            .map(|(&guesses, &count)| u64::from(guesses) * u64::from(count))
+            .sum();
+        total as f64 / f64::from(self.games().max(1))
+    }
+
+    /// Prints a histogram of guesses needed, marking the ones over the lives budget.
+    pub fn write_report<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let games = self.games();
+        writeln!(
+            output,
+            "{}",
+            tr_with(
+                "benchmark",
+                &[("games", &games), ("settings", &self.settings)]
+            )
+        )?;
+        // The same widths as the rows below, so the headings sit over their columns.
+        writeln!(
+            output,
+            "{:>7}  {:>5}  {:>6}",
+            tr("benchmark_guesses"),
+            tr("benchmark_games"),
+            tr("benchmark_share")
+        )?;
+
+        for (&guesses, &count) in &self.counts {
+            let share = f64::from(count) * 100.0 / f64::from(games.max(1));
+            let bar = "#".repeat((share / 2.0).round() as usize);
+            let budget = if guesses > self.settings.lives {
+                format!("  {}", tr("benchmark_out_of_lives"))
+            } else {
+                String::new()
+            };
+            writeln!(
+                output,
+                "{guesses:>7}  {count:>5}  {share:>5.1}%  {bar}{budget}"
+            )?;
+        }
+
+        let wins = self.wins();
+        let percent = format!("{:.1}", f64::from(wins) * 100.0 / f64::from(games.max(1)));
+        writeln!(
+            output,
+            "{}",
+            tr_with(
+                "benchmark_wins",
+                &[
+                    ("lives", &self.settings.lives),
+                    ("wins", &wins),
+                    ("games", &games),
+                    ("percent", &percent)
+                ]
+            )
+        )?;
+        let average = format!("{:.2}", self.mean());
+        writeln!(
+            output,
+            "{}",
+            tr_with(
+                "benchmark_average",
+                &[
+                    ("average", &average),
+                    ("worst", &self.worst()),
+                    (
+                        "size",
+                        &(u64::from(self.settings.max - self.settings.min) + 1)
+                    ),
+                    ("needed", &worst_case(self.settings.min, self.settings.max))
+                ]
+            )
+        )
+    }
+}
+
+/// The most guesses binary search can need: the bits in the range size, ceil(log2(size + 1)).
+pub fn worst_case(min: u32, max: u32) -> u32 {
+    let size = u64::from(max - min) + 1;
+    u64::BITS - size.leading_zeros()
+}
@@ -0,0 +1,196 @@
+/*
+ * Timed play: a countdown for every guess and a clock for the whole game.
+ *
+ * read_line blocks until a whole line arrives, and there is no way to give it a timeout.
+ * So a separate thread does the blocking reads and sends each line through a channel,
+ * and the game waits on the channel with recv_timeout, which *can* give up.
+ *
+ *     reader thread:  read_line -> send ---channel---> recv_timeout :game loop
+ *
+ * TimedInput also implements BufRead (waiting as long as it takes), so questions
+ * that have no clock, like "Play again?", can read from it like from stdin.
+ */
+use std::io::{self, BufRead, Read, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::color::{Color, paint};
+use crate::game::State;
+use crate::i18n::{tr, tr_with};
+use crate::play::{Puzzle, announce_end};
+
+/// What waiting for a line produced.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Line {
+    Text(String),
+    /// Nothing arrived in time.
+    TimedOut,
+    /// The input ended (EOF).
+    Closed,
+}
+
+/// Lines from a reader running on its own thread, so reading them can time out.
+pub struct TimedInput {
+    lines: Receiver<io::Result<String>>,
+    /// A line read through BufRead that was not used up yet, and how much of it was.
+    pending: String,
+    consumed: usize,
+}
+
+impl TimedInput {
+    /// Starts a thread that reads `reader` line by line.
+    ///
+    /// `Send + 'static` is required because the reader moves to the new thread
+    /// and may outlive the function that created it.
+    pub fn spawn<R: BufRead + Send + 'static>(mut reader: R) -> Self {
+        let (sender, lines) = mpsc::channel();
+
+        thread::spawn(move || {
+            loop {
+                let mut line = String::new();
+                let result = match reader.read_line(&mut line) {
+                    // EOF: returning drops the sender, which the receiver sees as Closed.
+                    Ok(0) => return,
+                    Ok(_) => Ok(line),
+                    Err(error) => Err(error),
+                };
+                let failed = result.is_err();
+                // A send error means the game is over and nobody is listening any more.
+                if sender.send(result).is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            lines,
+            pending: String::new(),
+            consumed: 0,
+        }
+    }
+
+    /// Waits for the next line, for at most `timeout` if one is given.
+    ///
+    /// (Not called read_line, so it can't be mixed up with BufRead::read_line.)
+    pub fn next_line(&mut self, timeout: Option<Duration>) -> io::Result<Line> {
+        if self.consumed < self.pending.len() {
+            let rest = self.pending[self.consumed..].to_string();
+            self.consumed = self.pending.len();
+            return Ok(Line::Text(rest));
+        }
+
+        let received = match timeout {
+            Some(timeout) => self.lines.recv_timeout(timeout),
+            None => self
+                .lines
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(line) => line.map(Line::Text),
+            Err(RecvTimeoutError::Timeout) => Ok(Line::TimedOut),
+            Err(RecvTimeoutError::Disconnected) => Ok(Line::Closed),
+        }
+    }
+}
+
+/*
+ * BufRead needs Read, so we implement both. fill_buf hands out what is left
+ * of the current line, waiting for the next one when it is used up.
+ */
+impl Read for TimedInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+impl BufRead for TimedInput {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.consumed == self.pending.len() {
+            match self.next_line(None)? {
+                Line::Text(line) => {
+                    self.pending = line;
+                    self.consumed = 0;
+                }
+                // An empty slice is how BufRead says EOF. Without a timeout, nothing can time out.
+                Line::Closed | Line::TimedOut => return Ok(&[]),
+            }
+        }
+        Ok(&self.pending.as_bytes()[self.consumed..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.consumed = (self.consumed + amount).min(self.pending.len());
+    }
+}
+
+/// How long to wait for a guess: until this guess's countdown or the game clock runs out,
+/// whichever comes first. `None` means there is no clock at all.
+pub(crate) fn wait_for(turn_limit: Option<Duration>, clock: Option<Duration>) -> Option<Duration> {
+    match (turn_limit, clock) {
+        (Some(turn), Some(clock)) => Some(turn.min(clock)),
+        (turn, clock) => turn.or(clock),
+    }
+}
+
+/// Plays `game` with its clocks running: a slow guess costs a life, and the game clock ends it.
+///
+/// Any `Puzzle` can be played this way, the number game and the code-breaking game alike.
+pub fn play<P: Puzzle, W: Write>(
+    game: &mut P,
+    input: &mut TimedInput,
+    output: &mut W,
+) -> io::Result<State> {
+    let started = Instant::now();
+    let (time_limit, turn_limit) = (game.time_limit(), game.turn_limit());
+
+    while !announce_end(game, output)? {
+        // Time left on the game clock, if there is one.
+        let clock = time_limit.map(|limit| limit.saturating_sub(started.elapsed()));
+        if clock == Some(Duration::ZERO) {
+            writeln!(output, "{}", tr("times_up"))?;
+            game.forfeit();
+            continue;
+        }
+
+        let mut question = tr_with("prompt", &[("lives", &game.lives())]);
+        if let Some(turn) = turn_limit {
+            question.push_str(&tr_with(
+                "prompt_turn_limit",
+                &[("seconds", &turn.as_secs())],
+            ));
+        }
+        if let Some(clock) = clock {
+            let seconds = clock.as_secs_f64().ceil();
+            question.push_str(&tr_with("prompt_clock", &[("seconds", &seconds)]));
+        }
+        writeln!(output, "{question}.")?;
+        output.flush()?;
+
+        let wait = wait_for(turn_limit, clock);
+
+        match input.next_line(wait)? {
+            Line::Text(line) => game.answer(&line, output)?,
+            Line::Closed => {
+                writeln!(output, "\n{}", tr("no_more_input"))?;
+                game.abandon();
+            }
+            Line::TimedOut if clock.is_some() && wait == clock => {
+                writeln!(output, "\n{}", tr("times_up"))?;
+                game.forfeit();
+            }
+            Line::TimedOut => {
+                writeln!(output, "\n{}", paint(&tr("too_slow"), Color::Red))?;
+                game.miss();
+            }
+        }
+    }
+
+    Ok(game.state())
+}
@@ -0,0 +1,99 @@
+/*
+ * A session of several rounds, played one after another without restarting:
+ * either until the player stops (the "Play again?" question) or for `--rounds N`.
+ * The Scoreboard keeps a line per round and prints the totals at the end.
+ */
+use std::fmt;
+
+use crate::game::{Game, State};
+use crate::i18n::{tr, tr_with};
+
+/// How one round went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub won: bool,
+    pub guesses: u32,
+    pub secret: u32,
+}
+
+/// Every round played so far in this session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scoreboard {
+    rounds: Vec<Round>,
+}
+
+impl Scoreboard {
+    /// Adds a finished game as the next round.
+    pub fn record(&mut self, game: &Game) {
+        self.rounds.push(Round {
+            won: game.state() == State::Won,
+            guesses: game.guesses().len() as u32,
+            secret: game.secret(),
+        });
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    pub fn wins(&self) -> usize {
+        self.rounds.iter().filter(|round| round.won).count()
+    }
+
+    pub fn losses(&self) -> usize {
+        self.rounds.len() - self.wins()
+    }
+
+    /// Guesses per round, on average. `None` before the first round.
+    pub fn average_guesses(&self) -> Option<f64> {
+        if self.rounds.is_empty() {
+            return None;
+        }
+        let total: u32 = self.rounds.iter().map(|round| round.guesses).sum();
+        Some(f64::from(total) / self.rounds.len() as f64)
+    }
+}
+
+impl fmt::Display for Scoreboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", tr("scoreboard"))?;
+        for (number, round) in self.rounds.iter().enumerate() {
+            let result = tr(if round.won {
+                "scoreboard_won"
+            } else {
+                "scoreboard_lost"
+            });
+            // Padded to two places so the columns line up.
+            let line = tr_with(
+                "scoreboard_round",
+                &[
+                    ("round", &format!("{:>2}", number + 1)),
+                    ("result", &result),
+                    ("guesses", &format!("{:>2}", round.guesses)),
+                    ("secret", &round.secret),
+                ],
+            );
+            writeln!(f, "  {line}")?;
+        }
+        let totals = tr_with(
+            "scoreboard_totals",
+            &[
+                ("wins", &self.wins()),
+                ("losses", &self.losses()),
+                ("rounds", &self.rounds.len()),
+            ],
+        );
+        writeln!(f, "  {totals}")?;
+        match self.average_guesses() {
+            Some(average) => {
+                let average = format!("{average:.1}");
+                write!(
+                    f,
+                    "  {}",
+                    tr_with("scoreboard_average", &[("average", &average)])
+                )
+            }
+            None => write!(f, "  {}", tr("scoreboard_empty")),
+        }
+    }
+}
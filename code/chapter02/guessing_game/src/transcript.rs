@@ -0,0 +1,223 @@
+/*
+ * What happened in a game, for the player at the end (`Summary`)
+ * and for instructors afterwards (`--transcript game.json` or `game.csv`).
+ *
+ * Both formats are simple enough to write by hand with write!,
+ * so we don't pull in a serialization crate.
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::game::{Game, State, Turn};
+use crate::i18n::{tr, tr_with};
+use crate::solver;
+
+/// The file formats a transcript can be saved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Picks the format from the file extension: `.json` or `.csv`.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+
+        match extension.as_deref() {
+            Some("json") => Ok(Format::Json),
+            Some("csv") => Ok(Format::Csv),
+            _ => Err(format!(
+                "can't tell the transcript format of '{}', use a .json or .csv file",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// The end-of-game numbers shown to the player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub won: bool,
+    pub guesses: u32,
+    pub duration: Duration,
+    /// The most guesses a perfect binary search needs over the whole range.
+    pub optimal_worst_case: u32,
+    /// The guesses a binary search would have needed for this secret.
+    pub optimal_for_secret: u32,
+}
+
+impl Summary {
+    pub fn new(game: &Game) -> Self {
+        let settings = game.settings();
+        Self {
+            won: game.state() == State::Won,
+            guesses: game.guesses().len() as u32,
+            duration: game.elapsed(),
+            optimal_worst_case: solver::worst_case(settings.min, settings.max),
+            optimal_for_secret: solver::guesses_needed(settings.min, settings.max, game.secret()),
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = format!("{:.1}", self.duration.as_secs_f64());
+        let result = if self.won {
+            "summary_won"
+        } else {
+            "summary_lost"
+        };
+
+        writeln!(f, "{}", tr("summary"))?;
+        writeln!(
+            f,
+            "  {}",
+            tr_with(result, &[("guesses", &self.guesses), ("seconds", &seconds)])
+        )?;
+        write!(
+            f,
+            "  {}",
+            tr_with(
+                "summary_optimal",
+                &[
+                    ("worst", &self.optimal_worst_case),
+                    ("needed", &self.optimal_for_secret)
+                ]
+            )
+        )
+    }
+}
+
+/// The message key for how a guess compared, for the player to read: "too small" and so on.
+pub fn result_key(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "result_too_small",
+        Ordering::Greater => "result_too_big",
+        Ordering::Equal => "result_correct",
+    }
+}
+
+/// How a guess compared, as written in transcripts and batch output.
+pub fn result_name(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "too small",
+        Ordering::Greater => "too big",
+        Ordering::Equal => "correct",
+    }
+}
+
+/// How a turn ended, as written in transcripts: its guess's result, or "timed out".
+fn turn_result(turn: &Turn) -> &'static str {
+    turn.ordering.map_or("timed out", result_name)
+}
+
+/// Saves the game to `path`, in the format its extension asks for.
+pub fn save(path: &Path, game: &Game, player: &str) -> io::Result<()> {
+    let format = Format::from_path(path)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidInput, message))?;
+    let mut file = BufWriter::new(File::create(path)?);
+
+    match format {
+        Format::Json => write_json(&mut file, game, player)?,
+        Format::Csv => write_csv(&mut file, game)?,
+    }
+    file.flush()
+}
+
+/// One row per turn: turn, guess, result, milliseconds since the start and lives left.
+/// A turn that timed out has an empty guess.
+pub fn write_csv<W: Write>(output: &mut W, game: &Game) -> io::Result<()> {
+    writeln!(output, "turn,guess,result,elapsed_ms,lives_left")?;
+    for (number, turn) in game.turns().iter().enumerate() {
+        writeln!(
+            output,
+            "{},{},{},{},{}",
+            number + 1,
+            turn.guess
+                .map(|guess| guess.to_string())
+                .unwrap_or_default(),
+            turn_result(turn),
+            turn.elapsed.as_millis(),
+            turn.lives
+        )?;
+    }
+    Ok(())
+}
+
+/// The whole game as one JSON object: the rules, the result and every guess.
+pub fn write_json<W: Write>(output: &mut W, game: &Game, player: &str) -> io::Result<()> {
+    let settings = game.settings();
+    let summary = Summary::new(game);
+
+    writeln!(output, "{{")?;
+    writeln!(output, "  \"player\": {},", json_string(player))?;
+    writeln!(output, "  \"difficulty\": \"{}\",", settings.difficulty)?;
+    writeln!(output, "  \"min\": {},", settings.min)?;
+    writeln!(output, "  \"max\": {},", settings.max)?;
+    writeln!(output, "  \"lives\": {},", settings.lives)?;
+    writeln!(output, "  \"secret\": {},", game.secret())?;
+    writeln!(output, "  \"won\": {},", summary.won)?;
+    writeln!(
+        output,
+        "  \"duration_ms\": {},",
+        summary.duration.as_millis()
+    )?;
+    writeln!(
+        output,
+        "  \"optimal_worst_case\": {},",
+        summary.optimal_worst_case
+    )?;
+    writeln!(
+        output,
+        "  \"optimal_for_secret\": {},",
+        summary.optimal_for_secret
+    )?;
+    writeln!(output, "  \"guesses\": [")?;
+
+    let turns = game.turns();
+    for (number, turn) in turns.iter().enumerate() {
+        // JSON does not allow a comma after the last item.
+        let comma = if number + 1 < turns.len() { "," } else { "" };
+        writeln!(
+            output,
+            "    {{\"turn\": {}, \"guess\": {}, \"result\": \"{}\", \"elapsed_ms\": {}, \"lives_left\": {}}}{comma}",
+            number + 1,
+            // A turn that timed out has no guess: null in JSON.
+            turn.guess
+                .map_or(String::from("null"), |guess| guess.to_string()),
+            turn_result(turn),
+            turn.elapsed.as_millis(),
+            turn.lives
+        )?;
+    }
+
+    writeln!(output, "  ]")?;
+    writeln!(output, "}}")
+}
+
+/// Quotes `text` as a JSON string, escaping what JSON requires.
+pub fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
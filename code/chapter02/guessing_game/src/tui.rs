@@ -0,0 +1,227 @@
+/*
+ * A full-screen view of the game, redrawn after every guess.
+ *
+ * Terminals understand "ANSI escape codes": text starting with the ESC character (\x1b)
+ * that moves the cursor or clears the screen instead of being printed.
+ * - \x1b[2J clears the whole screen
+ * - \x1b[H  moves the cursor to the top-left corner
+ * That is all we need, so no extra crate is required.
+ */
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, State};
+use crate::hints::Hint;
+use crate::i18n::{tr, tr_with};
+use crate::play::not_counted;
+use crate::solver::Solver;
+use crate::timed::{self, Line, TimedInput};
+use crate::transcript::result_key;
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const BAR_WIDTH: usize = 50;
+
+/// Plays `game` like `play::play`, but redraws the whole screen after every guess.
+///
+/// read_line can't time out, so the game clock is only checked after every answer
+/// and a turn limit can't be kept: timed games use `play_timed`.
+pub fn play<R, W>(game: &mut Game, input: &mut R, output: &mut W) -> io::Result<State>
+where
+    R: BufRead,
+    W: Write,
+{
+    run(game, output, |_| {
+        let mut line = String::new();
+        Ok(match input.read_line(&mut line)? {
+            0 => Line::Closed,
+            _ => Line::Text(line),
+        })
+    })
+}
+
+/// Like `play`, with the clocks running as in `timed::play`: a slow guess costs a life.
+pub fn play_timed<W: Write>(
+    game: &mut Game,
+    input: &mut TimedInput,
+    output: &mut W,
+) -> io::Result<State> {
+    run(game, output, |wait| input.next_line(wait))
+}
+
+/// The loop `play` and `play_timed` share. `next_line` waits at most the time it is given.
+fn run<W, F>(game: &mut Game, output: &mut W, mut next_line: F) -> io::Result<State>
+where
+    W: Write,
+    F: FnMut(Option<Duration>) -> io::Result<Line>,
+{
+    let started = Instant::now();
+    let settings = game.settings().clone();
+    let mut message = tr("tui_start");
+
+    loop {
+        write!(output, "{CLEAR_SCREEN}{}", render(game, &message))?;
+        if game.is_over() {
+            break;
+        }
+
+        // Time left on the game clock, if there is one.
+        let clock = settings
+            .time_limit
+            .map(|limit| limit.saturating_sub(started.elapsed()));
+        if clock == Some(Duration::ZERO) {
+            message = tr("times_up");
+            game.forfeit();
+            continue;
+        }
+
+        match settings.turn_limit {
+            Some(turn) => write!(output, "[{}s] > ", turn.as_secs())?,
+            None => write!(output, "> ")?,
+        }
+        output.flush()?;
+
+        let wait = timed::wait_for(settings.turn_limit, clock);
+        let guess = match next_line(wait)? {
+            Line::Text(line) => line,
+            Line::Closed => {
+                message = tr("no_more_input");
+                game.abandon();
+                continue;
+            }
+            Line::TimedOut if clock.is_some() && wait == clock => {
+                message = tr("times_up");
+                game.forfeit();
+                continue;
+            }
+            Line::TimedOut => {
+                message = tr("too_slow");
+                game.miss();
+                continue;
+            }
+        };
+
+        if let Some(limit) = settings.time_limit
+            && started.elapsed() > limit
+        {
+            message = tr("times_up");
+            game.forfeit();
+            continue;
+        }
+
+        let ordering = match game.parse_guess(&guess).and_then(|guess| game.guess(guess)) {
+            Ok(ordering) => ordering,
+            Err(error) => {
+                message = not_counted(&error);
+                continue;
+            }
+        };
+
+        message = match ordering {
+            Ordering::Less => tr("too_small"),
+            Ordering::Greater => tr("too_big"),
+            Ordering::Equal => String::new(),
+        };
+        if game.settings().hints
+            && let Some(hint) = Hint::for_any(game.secret(), game.guesses())
+        {
+            message = format!("{message} {hint}");
+        }
+    }
+
+    Ok(game.state())
+}
+
+/// Draws the screen for the current state of `game`, with `message` under the history.
+pub fn render(game: &Game, message: &str) -> String {
+    let settings = game.settings();
+    let mut screen = String::new();
+
+    screen.push_str(&format!("{}  ({settings})\n\n", tr("tui_title")));
+
+    // The labels are padded to the same width, whatever the language makes them.
+    let (lives, range) = (tr("tui_lives"), tr("tui_range"));
+    let label = lives.chars().count().max(range.chars().count());
+    screen.push_str(&format!(
+        "{lives:<label$}  {}\n",
+        hearts(game.lives(), settings.lives)
+    ));
+
+    let remaining = remaining(game);
+    screen.push_str(&format!(
+        "{range:<label$}  [{}]  {}\n",
+        range_bar(settings.min, settings.max, remaining),
+        match remaining {
+            Some((low, high)) if low == high => format!("{low}"),
+            Some((low, high)) => format!("{low}..={high}"),
+            None => String::from("-"),
+        }
+    ));
+    let max_label = settings.max.to_string();
+    // The min and max go under the ends of the bar, which starts after "Range  [".
+    screen.push_str(&format!(
+        "{:indent$}{:<width$}{max_label}\n\n",
+        "",
+        settings.min,
+        indent = label + 3,
+        width = (BAR_WIDTH + 2).saturating_sub(max_label.len())
+    ));
+
+    screen.push_str(&format!("{}\n", tr("tui_history")));
+    if game.guesses().is_empty() {
+        screen.push_str(&format!("  {}\n", tr("tui_no_guesses")));
+    }
+    for (number, &guess) in game.guesses().iter().enumerate() {
+        let verdict = tr(result_key(guess.cmp(&game.secret())));
+        screen.push_str(&format!("  {:>2}. {guess:>10}  {verdict}\n", number + 1));
+    }
+    screen.push('\n');
+
+    let secret = tr_with("secret_number_was", &[("secret", &game.secret())]);
+    match game.state() {
+        State::Won => screen.push_str(&format!("{}\n", tr("you_win"))),
+        State::Lost => screen.push_str(&format!("{message}\n{} {secret}\n", tr("you_lose"))),
+        State::Abandoned => screen.push_str(&format!("{message}\n{secret}\n")),
+        State::Playing => screen.push_str(&format!("{message}\n")),
+    }
+
+    screen
+}
+
+/// Full hearts for the lives left, empty ones for the lives already lost.
+pub fn hearts(lives: u32, total: u32) -> String {
+    let lost = total.saturating_sub(lives) as usize;
+    format!("{}{}", "♥".repeat(lives as usize), "♡".repeat(lost))
+}
+
+/// The numbers still possible after every guess so far, as `(low, high)`.
+fn remaining(game: &Game) -> Option<(u32, u32)> {
+    let settings = game.settings();
+    let mut solver = Solver::new(settings.min, settings.max);
+    for &guess in game.guesses() {
+        solver.learn(guess, guess.cmp(&game.secret()));
+    }
+    solver.remaining()
+}
+
+/// A bar across `min..=max` with the still-possible part filled in.
+pub fn range_bar(min: u32, max: u32, remaining: Option<(u32, u32)>) -> String {
+    let Some((low, high)) = remaining else {
+        return "·".repeat(BAR_WIDTH);
+    };
+
+    // u64 so the multiplication below can't overflow for big u32 ranges.
+    let size = u64::from(max - min) + 1;
+    let column = |number: u32| (u64::from(number - min) * BAR_WIDTH as u64 / size) as usize;
+    let (first, last) = (column(low), column(high));
+
+    (0..BAR_WIDTH)
+        .map(|position| {
+            if (first..=last).contains(&position) {
+                '█'
+            } else {
+                '·'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::PathBuf;
+
+use guessing_game::adaptive::{self, Adaptation, Change};
+use guessing_game::scores::Score;
+use guessing_game::{Difficulty, Settings};
+
+fn history(results: &str) -> Vec<Score> {
+    results
+        .chars()
+        .map(|result| Score {
+            name: String::from("amy"),
+            difficulty: Difficulty::Normal,
+            won: result == 'W',
+            guesses: 4,
+            date: String::from("2026-10-01"),
+        })
+        .collect()
+}
+
+fn normal() -> Settings {
+    Difficulty::Normal.settings()
+}
+
+#[test]
+fn no_history_plays_the_difficulty_as_it_is() {
+    let adaptation = Adaptation::learn(&[]);
+
+    assert_eq!(adaptation.level, 0);
+    assert_eq!(adaptation.apply(&normal()), normal());
+}
+
+#[test]
+fn winning_streaks_raise_the_level() {
+    let adaptation = Adaptation::learn(&history("WWWLWWWW"));
+
+    assert_eq!(adaptation.level, 2);
+    assert_eq!(
+        adaptation.changes,
+        [Change { game: 3, level: 1 }, Change { game: 7, level: 2 }]
+    );
+    assert_eq!(adaptation.wins_in_a_row, 1);
+
+    let settings = adaptation.apply(&normal());
+    assert_eq!(settings.difficulty, Difficulty::Custom);
+    assert_eq!((settings.min, settings.max, settings.lives), (1, 200, 4));
+}
+
+#[test]
+fn losing_streaks_ease_off() {
+    let adaptation = Adaptation::learn(&history("WWWLLLL"));
+
+    assert_eq!(adaptation.level, -1);
+    let settings = adaptation.apply(&normal());
+    assert_eq!((settings.min, settings.max, settings.lives), (1, 100, 6));
+}
+
+#[test]
+fn the_level_stays_within_its_limits() {
+    let top = Adaptation::learn(&history(&"W".repeat(60)));
+    assert_eq!(top.level, adaptive::MAX_LEVEL);
+    let settings = top.apply(&normal());
+    assert_eq!((settings.max, settings.lives), (800, 3));
+
+    let bottom = Adaptation::learn(&history(&"L".repeat(60)));
+    assert_eq!(bottom.level, adaptive::MIN_LEVEL);
+}
+
+#[test]
+fn a_reset_skips_earlier_games() {
+    let dir = std::env::temp_dir().join(format!("guessing_game-{}-reset", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path: PathBuf = adaptive::resets_path(&dir.join("scores.tsv"));
+
+    assert_eq!(adaptive::load_reset(&path, "amy").unwrap(), 0);
+    adaptive::save_reset(&path, "amy", 3).unwrap();
+    adaptive::save_reset(&path, "bob", 1).unwrap();
+    adaptive::save_reset(&path, "amy", 6).unwrap();
+    assert_eq!(adaptive::load_reset(&path, "amy").unwrap(), 6);
+
+    let scores = history("WWWWWWL");
+    assert_eq!(adaptive::history(&scores, "amy", 6).len(), 1);
+    assert!(adaptive::history(&scores, "bob", 0).is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn every_score_file_has_its_own_resets() {
+    let dir = PathBuf::from("games");
+
+    assert_eq!(
+        adaptive::resets_path(&dir.join("scores.tsv")),
+        dir.join("scores.resets.tsv")
+    );
+    assert_eq!(
+        adaptive::resets_path(&dir.join("kiosk.tsv")),
+        dir.join("kiosk.resets.tsv")
+    );
+}
+
+#[test]
+fn names_match_the_way_they_are_saved() {
+    // The score file saves control characters as spaces.
+    let mut scores = history("WW");
+    scores[0].name = String::from("amy lee");
+    scores[1].name = String::from("amy lee");
+
+    assert_eq!(adaptive::history(&scores, "amy\tlee", 0).len(), 2);
+}
@@ -0,0 +1,77 @@
+use guessing_game::batch::{self, EXIT_LOST, EXIT_WON, parse_list};
+use guessing_game::{Difficulty, Game, State};
+
+fn run(secret: u32, guesses: &[&str]) -> (State, Vec<String>) {
+    let mut game = Game::new(Difficulty::Normal.settings(), secret);
+    let mut output = Vec::new();
+    let state = batch::run(
+        &mut game,
+        guesses.iter().map(|guess| guess.to_string()),
+        &mut output,
+    )
+    .unwrap();
+    let lines = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    (state, lines)
+}
+
+#[test]
+fn lists_are_split_on_commas() {
+    assert_eq!(parse_list("50, 25,37"), ["50", "25", "37"]);
+    assert_eq!(parse_list(" 7 ,, "), ["7"]);
+    assert!(parse_list("").is_empty());
+}
+
+#[test]
+fn every_guess_is_one_json_line() {
+    let (state, lines) = run(37, &["50", "25", "37"]);
+
+    assert_eq!(state, State::Won);
+    assert_eq!(
+        lines,
+        [
+            r#"{"guess": 50, "result": "too big", "lives_left": 4}"#,
+            r#"{"guess": 25, "result": "too small", "lives_left": 3}"#,
+            r#"{"guess": 37, "result": "correct", "lives_left": 3}"#,
+            r#"{"state": "won", "secret": 37, "guesses": 3}"#,
+        ]
+    );
+    assert_eq!(batch::exit_code(state), EXIT_WON);
+}
+
+#[test]
+fn rejected_guesses_are_reported_and_free() {
+    let (_, lines) = run(37, &["a\"b", "50", "50"]);
+
+    assert_eq!(
+        lines[0],
+        r#"{"input": "a\"b", "error": "'a\"b' is not a number. Please type a whole number.", "lives_left": 5}"#
+    );
+    assert_eq!(
+        lines[2],
+        r#"{"input": "50", "error": "You already guessed 50.", "lives_left": 4}"#
+    );
+}
+
+#[test]
+fn running_out_of_guesses_loses() {
+    let (state, lines) = run(37, &["1", "2"]);
+
+    assert_eq!(state, State::Lost);
+    assert_eq!(
+        lines.last().unwrap(),
+        r#"{"state": "lost", "secret": 37, "guesses": 2}"#
+    );
+    assert_eq!(batch::exit_code(state), EXIT_LOST);
+}
+
+#[test]
+fn guesses_after_the_end_are_ignored() {
+    let (state, lines) = run(37, &["37", "50"]);
+
+    assert_eq!(state, State::Won);
+    assert_eq!(lines.len(), 2);
+}
@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use guessing_game::Difficulty;
+use guessing_game::cli::{self, Options};
+
+fn parse(args: &[&str]) -> Result<Options, String> {
+    cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn no_flags_means_menu() {
+    let options = parse(&[]).unwrap();
+
+    assert_eq!(options.settings_for::<u32>(), Ok(None));
+}
+
+#[test]
+fn difficulty_flag_picks_a_preset() {
+    let options = parse(&["--difficulty", "easy"]).unwrap();
+
+    assert_eq!(
+        options.settings_for::<u32>(),
+        Ok(Some(Difficulty::Easy.settings()))
+    );
+}
+
+#[test]
+fn overrides_make_a_custom_game() {
+    let options = parse(&["-d", "hard", "--lives=3", "--time-limit", "30"]).unwrap();
+    let settings = options.settings_for::<u32>().unwrap().unwrap();
+
+    assert_eq!(settings.difficulty, Difficulty::Custom);
+    assert_eq!((settings.min, settings.max, settings.lives), (1, 1000, 3));
+    assert_eq!(settings.time_limit, Some(Duration::from_secs(30)));
+}
+
+#[test]
+fn numbers_pick_the_kind_of_range() {
+    let options = parse(&["--numbers", "signed", "--min", "-50", "--max", "50"]).unwrap();
+    let settings = options.settings_for::<i64>().unwrap().unwrap();
+    assert_eq!((settings.min, settings.max), (-50, 50));
+
+    let options = parse(&["--numbers=decimal", "--max", "2.5", "--tolerance", "0.1"]).unwrap();
+    let settings = options.settings_for::<f64>().unwrap().unwrap();
+    assert_eq!(
+        (settings.min, settings.max, settings.tolerance),
+        (1.0, 2.5, 0.1)
+    );
+
+    // Whole numbers still refuse a negative minimum.
+    assert!(
+        parse(&["--min", "-50"])
+            .unwrap()
+            .settings_for::<u32>()
+            .is_err()
+    );
+}
+
+#[test]
+fn numbers_only_work_for_the_classic_game() {
+    assert!(parse(&["--numbers", "ints"]).is_err());
+    assert!(parse(&["--numbers", "decimal", "--tui"]).is_err());
+    assert!(parse(&["reverse", "--numbers", "signed"]).is_err());
+    assert!(parse(&["--numbers", "signed", "--guesses", "1,2"]).is_err());
+    assert!(parse(&["--numbers", "whole", "--tui"]).is_ok());
+}
+
+#[test]
+fn bad_values_are_rejected() {
+    assert!(parse(&["--difficulty", "impossible"]).is_err());
+    assert!(parse(&["--lives", "many"]).is_err());
+    assert!(parse(&["--lives"]).is_err());
+    assert!(parse(&["--unknown"]).is_err());
+    assert!(
+        parse(&["--min", "10", "--max", "1"])
+            .unwrap()
+            .settings_for::<u32>()
+            .is_err()
+    );
+    assert!(
+        parse(&["--lives", "0"])
+            .unwrap()
+            .settings_for::<u32>()
+            .is_err()
+    );
+}
+
+#[test]
+fn seed_flag_is_read() {
+    assert_eq!(parse(&["--seed", "7"]).unwrap().seed(), Ok(Some(7)));
+}
+
+#[test]
+fn commands_take_an_address() {
+    use guessing_game::cli::Command;
+
+    assert_eq!(parse(&[]).unwrap().command, Command::Play);
+    assert_eq!(
+        parse(&["host", "--lives", "3"]).unwrap().command,
+        Command::Host(String::from("127.0.0.1:7878"))
+    );
+    assert_eq!(
+        parse(&["join", "10.0.0.2:9000"]).unwrap().command,
+        Command::Join(String::from("10.0.0.2:9000"))
+    );
+    assert_eq!(
+        parse(&["serve"]).unwrap().command,
+        Command::Serve(String::from("127.0.0.1:8080"))
+    );
+    assert!(parse(&["serve", "--turn-time", "5"]).is_err());
+    assert!(parse(&["dance"]).is_err());
+    assert!(parse(&["join", "a", "b"]).is_err());
+}
+
+#[test]
+fn speed_run_sets_both_clocks() {
+    let settings = parse(&["--speed-run"])
+        .unwrap()
+        .settings_for::<u32>()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(settings.turn_limit, Some(cli::SPEED_RUN_TURN));
+    assert_eq!(settings.time_limit, Some(cli::SPEED_RUN_GAME));
+    assert!(settings.is_timed());
+}
+
+#[test]
+fn turn_time_overrides_the_speed_run_countdown() {
+    let settings = parse(&["--speed-run", "--turn-time", "3"])
+        .unwrap()
+        .settings_for::<u32>()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(settings.turn_limit, Some(Duration::from_secs(3)));
+    assert!(
+        parse(&["--turn-time", "0"])
+            .unwrap()
+            .settings_for::<u32>()
+            .is_err()
+    );
+}
+
+#[test]
+fn codebreaker_takes_difficulty_and_lives() {
+    use guessing_game::cli::Command;
+
+    let options = parse(&["mastermind", "-d", "hard", "--lives", "4"]).unwrap();
+    let rules = options.codebreaker_rules().unwrap();
+
+    assert_eq!(options.command, Command::Codebreaker);
+    assert_eq!((rules.length, rules.highest, rules.lives), (5, 9, 4));
+    assert!(
+        parse(&["codebreaker", "--lives", "0"])
+            .unwrap()
+            .codebreaker_rules()
+            .is_err()
+    );
+}
+
+#[test]
+fn codebreaker_keeps_the_clocks() {
+    let rules = parse(&["codebreaker", "--speed-run", "--turn-time", "3"])
+        .unwrap()
+        .codebreaker_rules()
+        .unwrap();
+
+    assert_eq!(rules.turn_limit, Some(Duration::from_secs(3)));
+    assert_eq!(rules.time_limit, Some(cli::SPEED_RUN_GAME));
+    assert!(
+        parse(&["codebreaker", "--turn-time", "0"])
+            .unwrap()
+            .codebreaker_rules()
+            .is_err()
+    );
+}
+
+#[test]
+fn rounds_must_be_at_least_one() {
+    assert_eq!(parse(&["--rounds", "3"]).unwrap().rounds, Some(3));
+    assert_eq!(parse(&[]).unwrap().rounds, None);
+    assert!(parse(&["--rounds", "0"]).is_err());
+}
+
+#[test]
+fn lang_flag_picks_a_language() {
+    use guessing_game::i18n::Language;
+
+    assert_eq!(
+        parse(&["--lang", "de"]).unwrap().lang,
+        Some(Language::German)
+    );
+    assert!(parse(&["--lang", "xx"]).is_err());
+}
+
+#[test]
+fn guesses_and_batch_are_batch_modes() {
+    let options = parse(&["--guesses", "50,25"]).unwrap();
+
+    assert_eq!(
+        options.guesses,
+        Some(vec![String::from("50"), String::from("25")])
+    );
+    assert!(options.is_batch());
+    assert!(parse(&["--batch"]).unwrap().is_batch());
+    assert!(!parse(&[]).unwrap().is_batch());
+}
@@ -0,0 +1,146 @@
+use std::io::{self, BufReader, Read};
+use std::thread;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use guessing_game::codebreaker::{CodeError, Codebreaker, Feedback, Rules, score};
+use guessing_game::timed::{self, TimedInput};
+use guessing_game::{Difficulty, State, play};
+
+/// A reader that never produces anything, like a player who walked away.
+struct Silent;
+
+impl Read for Silent {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        thread::sleep(Duration::from_secs(3600));
+        Ok(0)
+    }
+}
+
+fn normal() -> Rules {
+    Rules::for_difficulty(Difficulty::Normal)
+}
+
+/// Plays a whole game against `secret`, typing `input`, and returns the final state and transcript.
+fn script(secret: &[u8], input: &str) -> (State, String) {
+    let mut game = Codebreaker::new(normal(), secret.to_vec());
+    let mut output = Vec::new();
+    let state = play::play(&mut game, &mut input.as_bytes(), &mut output).unwrap();
+    (state, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn score_counts_exact_and_misplaced_digits() {
+    let feedback = |exact, misplaced| Feedback { exact, misplaced };
+
+    assert_eq!(score(&[1, 2, 3, 4], &[1, 2, 3, 4]), feedback(4, 0));
+    assert_eq!(score(&[1, 2, 3, 4], &[4, 3, 2, 1]), feedback(0, 4));
+    assert_eq!(score(&[1, 2, 3, 4], &[1, 3, 5, 6]), feedback(1, 1));
+    // Each digit of the secret only matches once.
+    assert_eq!(score(&[1, 2, 3, 4], &[1, 1, 1, 1]), feedback(1, 0));
+    assert_eq!(score(&[1, 1, 2, 2], &[2, 2, 1, 1]), feedback(0, 4));
+}
+
+#[test]
+fn parse_guess_explains_bad_codes() {
+    let game = Codebreaker::new(normal(), vec![1, 2, 3, 4]);
+
+    assert_eq!(game.parse_guess("1234\n"), Ok(vec![1, 2, 3, 4]));
+    assert_eq!(game.parse_guess(" 1 2, 3 4 "), Ok(vec![1, 2, 3, 4]));
+    assert_eq!(game.parse_guess(""), Err(CodeError::Empty));
+    assert_eq!(
+        game.parse_guess("12a4"),
+        Err(CodeError::NotANumber(String::from("12a4")))
+    );
+    assert_eq!(
+        game.parse_guess("123"),
+        Err(CodeError::WrongLength {
+            expected: 4,
+            got: 3
+        })
+    );
+    assert_eq!(
+        game.parse_guess("1237"),
+        Err(CodeError::DigitOutOfRange {
+            digit: 7,
+            highest: 6
+        })
+    );
+}
+
+#[test]
+fn wrong_guesses_cost_lives_until_the_code_is_cracked() {
+    let mut game = Codebreaker::new(normal(), vec![1, 2, 3, 4]);
+
+    game.guess(&[1, 1, 1, 1]).unwrap();
+    assert_eq!(
+        game.guess(&[1, 1, 1, 1]),
+        Err(CodeError::Repeated(String::from("1111")))
+    );
+    game.guess(&[1, 2, 3, 4]).unwrap();
+
+    assert_eq!(play::Puzzle::lives(&game), 9);
+    assert_eq!(play::Puzzle::state(&game), State::Won);
+    assert_eq!(game.guess(&[4, 3, 2, 1]), Err(CodeError::GameOver));
+}
+
+#[test]
+fn scripted_game_is_won() {
+    let (state, output) = script(&[1, 2, 3, 4], "4321\nabcd\n1234\n");
+
+    assert_eq!(state, State::Won);
+    assert!(output.contains("0 exact, 4 misplaced."));
+    assert!(
+        output.contains(
+            "'abcd' is not a number. Please type a whole number. That didn't cost a life."
+        )
+    );
+    assert!(output.contains("You win!"));
+}
+
+#[test]
+fn scripted_game_is_lost_and_the_code_revealed() {
+    let mut rules = normal();
+    rules.lives = 2;
+    let mut game = Codebreaker::new(rules, vec![6, 6, 6, 6]);
+    let mut output = Vec::new();
+
+    let state = play::play(&mut game, &mut "1111\n2222\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(state, State::Lost);
+    assert!(
+        String::from_utf8(output)
+            .unwrap()
+            .contains("You lose! The secret code was 6666.")
+    );
+}
+
+#[test]
+fn slow_codes_cost_lives() {
+    let mut rules = normal();
+    rules.lives = 2;
+    rules.turn_limit = Some(Duration::from_millis(20));
+    let mut game = Codebreaker::new(rules, vec![1, 2, 3, 4]);
+    let mut input = TimedInput::spawn(BufReader::new(Silent));
+    let mut output = Vec::new();
+
+    let state = timed::play(&mut game, &mut input, &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(state, State::Lost);
+    assert!(game.guesses().is_empty());
+    assert_eq!(output.matches("Too slow! That cost a life.").count(), 2);
+    assert!(output.contains("You lose! The secret code was 1234."));
+}
+
+#[test]
+fn same_seed_same_code() {
+    let first = Codebreaker::random(normal(), &mut StdRng::seed_from_u64(9));
+    let second = Codebreaker::random(normal(), &mut StdRng::seed_from_u64(9));
+
+    assert_eq!(first.secret(), second.secret());
+    assert_eq!(first.secret().len(), 4);
+    assert!(first.secret().iter().all(|digit| (1..=6).contains(digit)));
+}
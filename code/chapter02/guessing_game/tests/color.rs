@@ -0,0 +1,14 @@
+// Colour is one switch for the whole program, so these tests live in their own file (and process).
+use guessing_game::color::{self, Color, paint};
+
+#[test]
+fn paint_only_colours_when_enabled() {
+    assert_eq!(paint("You win!", Color::Green), "You win!");
+
+    color::set_enabled(true);
+    assert_eq!(paint("You win!", Color::Green), "\x1b[32mYou win!\x1b[0m");
+    assert_eq!(paint("Too big!", Color::Yellow), "\x1b[33mToo big!\x1b[0m");
+
+    color::set_enabled(false);
+    assert!(!color::enabled());
+}
@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use guessing_game::Difficulty;
+use guessing_game::cli;
+use guessing_game::config::{self, Config};
+
+#[test]
+fn config_file_is_parsed() {
+    let config = config::parse(
+        "min = 1\nmax = 20\nlives = 6\nhints = true\nname = \"Room 4\"\ncolor = false\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            difficulty: None,
+            min: Some(1),
+            max: Some(20),
+            lives: Some(6),
+            hints: Some(true),
+            adaptive: None,
+            name: Some(String::from("Room 4")),
+            color: Some(false),
+            lang: None,
+        }
+    );
+    assert_eq!(config::parse("").unwrap(), Config::default());
+}
+
+#[test]
+fn mistakes_in_the_file_are_reported() {
+    assert!(config::parse("lifes = 3").unwrap_err().contains("lifes"));
+    assert!(config::parse("lives = \"many\"").is_err());
+}
+
+#[test]
+fn flags_override_the_config() {
+    let mut options = cli::parse(["--lives", "3", "--no-hints"].map(String::from)).unwrap();
+    let config =
+        config::parse("difficulty = \"hard\"\nlives = 9\nhints = true\nname = \"kiosk\"").unwrap();
+
+    options.apply_config(config).unwrap();
+    let settings = options.settings_for::<u32>().unwrap().unwrap();
+
+    assert_eq!(options.difficulty, Some(Difficulty::Hard));
+    assert_eq!(settings.lives, 3);
+    assert_eq!(options.hints, Some(false));
+    assert_eq!(options.player_name(), "kiosk");
+}
+
+#[test]
+fn a_chosen_difficulty_ignores_the_config_range() {
+    let mut options = cli::parse(["-d", "hard"].map(String::from)).unwrap();
+    let config = config::parse("min = 1\nmax = 20\nlives = 9").unwrap();
+
+    options.apply_config(config).unwrap();
+    let settings = options.settings_for::<u32>().unwrap().unwrap();
+
+    assert_eq!(settings, Difficulty::Hard.settings());
+}
+
+#[test]
+fn bad_difficulty_in_the_config_is_an_error() {
+    let mut options = cli::parse(Vec::new()).unwrap();
+    let config = config::parse("difficulty = \"impossible\"").unwrap();
+
+    assert!(options.apply_config(config).is_err());
+}
+
+#[test]
+fn only_a_chosen_config_file_has_to_exist() {
+    let missing = Path::new("/this/config/does/not/exist.toml");
+
+    assert_eq!(config::load(missing, false), Ok(Config::default()));
+    assert!(config::load(missing, true).is_err());
+}
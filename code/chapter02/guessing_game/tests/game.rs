@@ -0,0 +1,189 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use guessing_game::{Difficulty, Game, GuessError, State, play};
+
+fn normal() -> guessing_game::Settings {
+    Difficulty::Normal.settings()
+}
+
+/// Plays a whole game against `secret`, typing `input`, and returns the final state and transcript.
+fn script(secret: u32, input: &str) -> (State, String) {
+    let mut game = Game::new(normal(), secret);
+    let mut output = Vec::new();
+    let state = play::play(&mut game, &mut input.as_bytes(), &mut output).unwrap();
+    (state, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn guess_compares_with_the_secret() {
+    let mut game = Game::new(normal(), 42);
+
+    assert_eq!(game.guess(10), Ok(Ordering::Less));
+    assert_eq!(game.guess(90), Ok(Ordering::Greater));
+    assert_eq!(game.lives(), 3);
+    assert_eq!(game.state(), State::Playing);
+
+    assert_eq!(game.guess(42), Ok(Ordering::Equal));
+    assert_eq!(game.state(), State::Won);
+    assert_eq!(game.lives(), 3);
+    assert_eq!(game.guesses(), &[10, 90, 42]);
+}
+
+#[test]
+fn running_out_of_lives_loses() {
+    let mut game = Game::new(normal(), 42);
+
+    for guess in 1..=5 {
+        game.guess(guess).unwrap();
+    }
+
+    assert_eq!(game.lives(), 0);
+    assert_eq!(game.state(), State::Lost);
+    assert_eq!(game.guess(42), Err(GuessError::GameOver));
+}
+
+#[test]
+fn timed_out_turns_are_recorded() {
+    let mut game = Game::new(normal(), 42);
+
+    game.guess(10).unwrap();
+    game.miss();
+
+    assert_eq!(game.lives(), 3);
+    assert_eq!(game.guesses(), &[10]);
+    let turns = game.turns();
+    assert_eq!(turns.len(), 2);
+    assert!(!turns[0].timed_out());
+    assert!(turns[1].timed_out());
+    assert_eq!((turns[1].guess, turns[1].ordering), (None, None));
+    assert_eq!(turns[1].lives, 3);
+
+    for _ in 0..3 {
+        game.miss();
+    }
+    assert_eq!(game.state(), State::Lost);
+    assert_eq!(game.turns().len(), 5);
+    // Once the game is over, running out of time again changes nothing.
+    game.miss();
+    assert_eq!(game.turns().len(), 5);
+}
+
+#[test]
+fn scripted_win() {
+    let (state, transcript) = script(37, "50\n25\n37\n");
+
+    assert_eq!(state, State::Won);
+    assert_eq!(
+        transcript,
+        "\
+Please input your guess. You have 5 lives left.
+You guessed: 50
+Too big!
+Please input your guess. You have 4 lives left.
+You guessed: 25
+Too small!
+Please input your guess. You have 3 lives left.
+You guessed: 37
+You win!
+"
+    );
+}
+
+#[test]
+fn scripted_loss() {
+    let (state, transcript) = script(99, "1\n2\n3\n4\n5\n");
+
+    assert_eq!(state, State::Lost);
+    assert!(transcript.ends_with("Too small!\nYou lose! The secret number was 99.\n"));
+}
+
+#[test]
+fn bad_input_is_explained_and_free() {
+    let (state, transcript) = script(7, "seven\n-3\n500\n\n3\n3\n7\n");
+
+    assert_eq!(state, State::Won);
+    assert!(transcript.contains("'seven' is not a number."));
+    assert!(transcript.contains("The secret number is never negative."));
+    assert!(transcript.contains("The secret number is between 1 and 100."));
+    assert!(transcript.contains("Please type a number."));
+    assert!(transcript.contains("You already guessed 3."));
+    assert_eq!(transcript.matches("That didn't cost a life.").count(), 5);
+    // Only the first 3 cost a life.
+    assert!(transcript.contains("You have 4 lives left.\nYou guessed: 7\nYou win!\n"));
+}
+
+#[test]
+fn parse_guess_explains_every_problem() {
+    let game = Game::new(normal(), 50);
+
+    assert_eq!(game.parse_guess(" 42\n"), Ok(42));
+    assert_eq!(game.parse_guess(""), Err(GuessError::Empty));
+    assert_eq!(
+        game.parse_guess("4.5"),
+        Err(GuessError::NotANumber(String::from("4.5")))
+    );
+    assert_eq!(game.parse_guess("-1"), Err(GuessError::Negative));
+    let out_of_range = Err(GuessError::OutOfRange { min: 1, max: 100 });
+    assert_eq!(game.parse_guess("0"), out_of_range);
+    assert_eq!(game.parse_guess("99999999999"), out_of_range);
+}
+
+#[test]
+fn rejected_guesses_keep_every_life() {
+    let mut game = Game::new(normal(), 50);
+    game.guess(10).unwrap();
+
+    assert_eq!(game.guess(10), Err(GuessError::Repeated(10)));
+    assert_eq!(
+        game.guess(101),
+        Err(GuessError::OutOfRange { min: 1, max: 100 })
+    );
+    assert_eq!(game.lives(), 4);
+    assert_eq!(game.guesses(), &[10]);
+}
+
+#[test]
+fn end_of_input_abandons_the_game() {
+    let (state, transcript) = script(7, "3\n");
+
+    // Running out of input is not a loss, so it isn't saved as one.
+    assert_eq!(state, State::Abandoned);
+    assert!(transcript.ends_with("No more input, giving up.\nThe secret number was 7.\n"));
+}
+
+#[test]
+fn same_seed_same_secrets() {
+    let secrets = |seed| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..10)
+            .map(|_| Game::random(normal(), &mut rng).secret())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(secrets(42), secrets(42));
+    assert!(secrets(42).iter().all(|secret| (1..=100).contains(secret)));
+}
+
+#[test]
+fn menu_picks_a_preset() {
+    let mut output = Vec::new();
+    let settings = play::choose_settings(&mut "hard\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(settings, Difficulty::Hard.settings());
+}
+
+#[test]
+fn menu_asks_for_custom_values() {
+    let mut output = Vec::new();
+    let settings =
+        play::choose_settings(&mut "4\n10\n20\n3\n0\n5\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(settings.difficulty, Difficulty::Custom);
+    assert_eq!((settings.min, settings.max, settings.lives), (10, 20, 3));
+    assert_eq!(settings.time_limit, None);
+    assert_eq!(settings.turn_limit, Some(Duration::from_secs(5)));
+}
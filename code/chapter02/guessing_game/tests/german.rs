@@ -0,0 +1,95 @@
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use guessing_game::adaptive::Adaptation;
+use guessing_game::i18n::{self, Language};
+use guessing_game::net::{self, Connection};
+use guessing_game::scores::Score;
+use guessing_game::{Difficulty, Game, State, tui};
+
+/*
+ * The language is shared by every test in a test binary, so these tests get a file of their own.
+ * All of them play in German and none switches back.
+ */
+
+#[test]
+fn a_network_game_in_german() {
+    i18n::set_language(Language::German);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let host = thread::spawn(move || -> io::Result<String> {
+        let (stream, _) = listener.accept()?;
+        let mut connection = Connection::new(stream)?;
+        let mut game = Game::new(Difficulty::Normal.settings(), 42);
+        let mut output = Vec::new();
+        net::host(&mut connection, &mut game, &mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    });
+
+    let mut connection = Connection::new(TcpStream::connect(address).unwrap()).unwrap();
+    let mut output = Vec::new();
+    let state = net::join(
+        &mut connection,
+        &mut "abc\n50\n42\n".as_bytes(),
+        &mut output,
+    );
+    let guest = String::from_utf8(output).unwrap();
+    let host = host.join().unwrap().unwrap();
+
+    assert_eq!(state.unwrap(), State::Won);
+    assert!(guest.starts_with("Errate die Zahl zwischen 1 und 100. Du hast 5 Leben.\n"));
+    assert!(guest.contains("Bitte gib deinen Tipp ein. Du hast noch 5 Leben: "));
+    assert!(guest.contains("'abc' ist keine Zahl."));
+    assert!(guest.contains("Das hat kein Leben gekostet."));
+    assert!(guest.contains("Zu groß!\n"));
+    assert!(guest.ends_with("Gewonnen!\n"));
+
+    assert!(host.contains("Getippt wurde 'abc': 'abc' ist keine Zahl."));
+    assert!(host.contains("Getippt wurde 50: zu groß. Noch 4 Leben."));
+    assert!(host.ends_with("Deine Zahl wurde gefunden!\n"));
+}
+
+#[test]
+fn the_full_screen_view_in_german() {
+    i18n::set_language(Language::German);
+    let mut settings = Difficulty::Normal.settings();
+    settings.lives = 2;
+    let mut game = Game::new(settings, 30);
+    let mut output = Vec::new();
+
+    let state = tui::play(&mut game, &mut "10\nx\n90\n".as_bytes(), &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(state, State::Lost);
+    assert!(output.contains("ERRATE DIE ZAHL"));
+    assert!(output.contains("Gib eine Zahl ein und drück Enter."));
+    // "Bereich" is longer than "Leben", so the hearts line up with the bar.
+    assert!(output.contains("Leben    ♥♡\n"));
+    assert!(output.contains("Bereich  ["));
+    assert!(output.contains("Verlauf\n"));
+    assert!(output.contains("10  zu klein\n"));
+    assert!(output.contains("'x' ist keine Zahl."));
+    assert!(output.ends_with("Zu groß!\nVerloren! Die geheime Zahl war 30.\n"));
+}
+
+#[test]
+fn the_adaptive_difficulty_explained_in_german() {
+    i18n::set_language(Language::German);
+    let history: Vec<Score> = "WWWL"
+        .chars()
+        .map(|result| Score {
+            name: String::from("amy"),
+            difficulty: Difficulty::Normal,
+            won: result == 'W',
+            guesses: 4,
+            date: String::from("2026-10-01"),
+        })
+        .collect();
+
+    let explanation = Adaptation::learn(&history).to_string();
+    assert!(explanation.starts_with("3 Siege in Folge erhöhen die Stufe"));
+    assert!(explanation.contains("  Spiel   3: 3 Siege in Folge, hoch auf Stufe 1.\n"));
+    assert!(explanation.ends_with("Nach 4 Spielen: Stufe 1, 1 von 2 Niederlagen in Folge."));
+}
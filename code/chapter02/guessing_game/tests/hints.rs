@@ -0,0 +1,57 @@
+use guessing_game::hints::{Band, Hint, Temperature};
+use guessing_game::{Difficulty, Game, play};
+
+#[test]
+fn bands_are_coarse_distances() {
+    assert_eq!(Band::from_distance(1), Band::Within5);
+    assert_eq!(Band::from_distance(5), Band::Within5);
+    assert_eq!(Band::from_distance(6), Band::Within20);
+    assert_eq!(Band::from_distance(50), Band::Within50);
+    assert_eq!(Band::from_distance(51), Band::Far);
+}
+
+#[test]
+fn first_guess_only_has_a_band() {
+    let hint = Hint::for_any(40u32, &[30]).unwrap();
+
+    assert_eq!(hint.band, Band::Within20);
+    assert_eq!(hint.temperature, None);
+    assert_eq!(hint.to_string(), "You are within 20.");
+}
+
+#[test]
+fn later_guesses_are_warmer_or_colder() {
+    assert_eq!(
+        Hint::for_any(40u32, &[10, 38]).unwrap().temperature,
+        Some(Temperature::Warmer)
+    );
+    assert_eq!(
+        Hint::for_any(40u32, &[38, 10]).unwrap().temperature,
+        Some(Temperature::Colder)
+    );
+    assert_eq!(
+        Hint::for_any(40u32, &[35, 45]).unwrap().temperature,
+        Some(Temperature::Same)
+    );
+}
+
+#[test]
+fn no_hint_for_the_right_answer() {
+    assert_eq!(Hint::for_any(40u32, &[10, 40]), None);
+    assert_eq!(Hint::for_any(40u32, &[]), None);
+}
+
+#[test]
+fn hints_only_show_when_enabled() {
+    let transcript = |hints| {
+        let mut settings = Difficulty::Normal.settings();
+        settings.hints = hints;
+        let mut game = Game::new(settings, 40);
+        let mut output = Vec::new();
+        play::play(&mut game, &mut "10\n38\n40\n".as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    assert!(transcript(true).contains("Too small!\nWarmer! You are within 5.\n"));
+    assert!(!transcript(false).contains("Warmer"));
+}
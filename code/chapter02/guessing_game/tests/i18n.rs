@@ -0,0 +1,94 @@
+use guessing_game::i18n::{self, Catalogue, Language, fill};
+use guessing_game::{Difficulty, Game, GuessError, play};
+
+#[test]
+fn languages_are_read_from_locales() {
+    assert_eq!(
+        Language::from_locale("es_ES.UTF-8"),
+        Some(Language::Spanish)
+    );
+    assert_eq!(Language::from_locale("de-AT"), Some(Language::German));
+    assert_eq!(Language::from_locale("EN"), Some(Language::English));
+    assert_eq!(Language::from_locale("fr_FR.UTF-8"), None);
+    assert_eq!(Language::from_locale("C"), None);
+    assert_eq!(Language::from_locale("desktop"), None);
+    assert_eq!(Language::from_locale("english"), None);
+    assert_eq!(Language::from_locale("de_"), Some(Language::German));
+    assert!("klingon".parse::<Language>().is_err());
+}
+
+#[test]
+fn missing_translations_fall_back_to_english() {
+    let catalogue = Catalogue::parse(&[
+        (
+            Language::English,
+            "too_small = \"Too small!\"\ntoo_big = \"Too big!\"",
+        ),
+        (Language::German, "too_small = \"Zu klein!\""),
+    ])
+    .unwrap();
+
+    assert_eq!(catalogue.get(Language::German, "too_small"), "Zu klein!");
+    assert_eq!(catalogue.get(Language::German, "too_big"), "Too big!");
+    // Spanish has no messages at all here, so everything is English.
+    assert_eq!(catalogue.get(Language::Spanish, "too_small"), "Too small!");
+    // A key nobody knows is shown as it is, so the mistake is easy to spot.
+    assert_eq!(
+        catalogue.get(Language::English, "no_such_key"),
+        "no_such_key"
+    );
+}
+
+#[test]
+fn placeholders_are_filled_in() {
+    let text = fill("Between {min} and {max}.", &[("min", &1), ("max", &"100")]);
+
+    assert_eq!(text, "Between 1 and 100.");
+}
+
+#[test]
+fn filled_in_values_are_not_filled_in_again() {
+    let text = fill(
+        "Guessed '{text}': {error}",
+        &[("text", &"{error}"), ("error", &"not a number")],
+    );
+
+    assert_eq!(text, "Guessed '{error}': not a number");
+    assert_eq!(fill("{unknown} {a}", &[("a", &"{a}")]), "{unknown} {a}");
+}
+
+#[test]
+fn built_in_translations_only_use_english_keys() {
+    let catalogue = Catalogue::builtin();
+
+    for language in Language::ALL {
+        assert_eq!(
+            catalogue.unknown_keys(language),
+            Vec::<&str>::new(),
+            "{language}"
+        );
+    }
+    assert_eq!(
+        catalogue.get(Language::Spanish, "too_big"),
+        "¡Demasiado grande!"
+    );
+}
+
+// The only test here that switches the language, since the choice is shared by every test.
+#[test]
+fn the_game_speaks_the_chosen_language() {
+    i18n::set_language(Language::Spanish);
+    let mut game = Game::new(Difficulty::Normal.settings(), 42);
+    let mut output = Vec::new();
+
+    play::play(&mut game, &mut "50\nabc\n42\n".as_bytes(), &mut output).unwrap();
+    let error = GuessError::OutOfRange { min: 1, max: 100 }.to_string();
+    i18n::set_language(Language::English);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Escribe tu número. Te quedan 5 vidas."));
+    assert!(output.contains("¡Demasiado grande!"));
+    assert!(output.contains("'abc' no es un número."));
+    assert!(output.contains("¡Has ganado!"));
+    assert_eq!(error, "El número secreto está entre 1 y 100.");
+}
@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use guessing_game::net::{self, Connection, Message};
+use guessing_game::{Difficulty, Game, State};
+
+/// Starts a host on a free localhost port and returns its address and the thread running it.
+fn start_host(secret: u32) -> (String, thread::JoinHandle<io::Result<State>>) {
+    // Port 0 asks the operating system for any free port.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let host = thread::spawn(move || {
+        let (stream, _) = listener.accept()?;
+        let mut connection = Connection::new(stream)?;
+        let mut game = Game::new(Difficulty::Normal.settings(), secret);
+        net::host(&mut connection, &mut game, &mut io::sink())
+    });
+
+    (address, host)
+}
+
+fn join(address: &str, input: &str) -> (io::Result<State>, String) {
+    let mut connection = Connection::new(TcpStream::connect(address).unwrap()).unwrap();
+    let mut output = Vec::new();
+    let state = net::join(&mut connection, &mut input.as_bytes(), &mut output);
+    (state, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn messages_round_trip() {
+    let messages = [
+        Message::Hello {
+            min: 1,
+            max: 100,
+            lives: 5,
+        },
+        Message::Turn { lives: 3 },
+        Message::Guess(String::from("fifty")),
+        Message::Result(Ordering::Greater),
+        Message::Invalid(String::from("The secret number is never negative.")),
+        Message::Hint(String::from("Warmer! You are within 5.")),
+        Message::Over {
+            won: false,
+            secret: 42,
+        },
+    ];
+
+    for message in messages {
+        assert_eq!(Message::parse(&format!("{message}\n")), Ok(message));
+    }
+}
+
+#[test]
+fn broken_messages_are_rejected() {
+    assert!(Message::parse("HELLO 1 100").is_err());
+    assert!(Message::parse("RESULT SIDEWAYS").is_err());
+    assert!(Message::parse("OVER DRAW 3").is_err());
+    assert!(Message::parse("DANCE").is_err());
+}
+
+#[test]
+fn guest_wins_over_localhost() {
+    let (address, host) = start_host(42);
+
+    let (state, transcript) = join(&address, "abc\n50\n42\n");
+
+    assert_eq!(state.unwrap(), State::Won);
+    assert_eq!(host.join().unwrap().unwrap(), State::Won);
+    assert!(transcript.contains("'abc' is not a number."));
+    assert!(transcript.contains("Too big!\n"));
+    assert!(transcript.ends_with("You win!\n"));
+}
+
+#[test]
+fn host_enforces_lives() {
+    let (address, host) = start_host(99);
+
+    let (state, transcript) = join(&address, "1\n2\n3\n4\n5\n6\n");
+
+    assert_eq!(state.unwrap(), State::Lost);
+    assert_eq!(host.join().unwrap().unwrap(), State::Lost);
+    assert!(transcript.ends_with("You lose! The secret number was 99.\n"));
+}
+
+#[test]
+fn host_notices_a_disconnect() {
+    let (address, host) = start_host(42);
+
+    // The guest runs out of input after one guess and hangs up.
+    let (state, _) = join(&address, "50\n");
+
+    assert_eq!(state.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    let error = host.join().unwrap().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+}
+
+#[test]
+fn host_can_type_or_skip_the_secret() {
+    let settings = Difficulty::Normal.settings();
+    let mut rng = rand::rng();
+    let mut output = Vec::new();
+
+    let typed = net::choose_secret(
+        &settings,
+        &mut rng,
+        &mut "500\n0\n64\n".as_bytes(),
+        &mut output,
+    );
+    assert_eq!(typed.unwrap(), 64);
+
+    let random = net::choose_secret(&settings, &mut rng, &mut "\n".as_bytes(), &mut output);
+    assert!((1..=100).contains(&random.unwrap()));
+}
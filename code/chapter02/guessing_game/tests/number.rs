@@ -0,0 +1,106 @@
+use std::cmp::Ordering;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use guessing_game::number::ParseError;
+use guessing_game::{Difficulty, Game, GuessError, Number, Settings, State};
+
+fn signed() -> Settings<i64> {
+    Settings {
+        min: -50,
+        max: 50,
+        ..Difficulty::Normal.settings().to()
+    }
+}
+
+fn decimal() -> Settings<f64> {
+    Difficulty::Normal.settings().to()
+}
+
+#[test]
+fn whole_numbers_keep_their_errors() {
+    assert_eq!(u32::parse("42"), Ok(42));
+    assert_eq!(u32::parse("-5"), Err(ParseError::Negative));
+    assert_eq!(u32::parse("99999999999"), Err(ParseError::TooBig));
+    assert_eq!(u32::parse("4.5"), Err(ParseError::NotANumber));
+}
+
+#[test]
+fn signed_numbers_can_be_negative() {
+    assert_eq!(i64::parse("-5"), Ok(-5));
+
+    let mut game = Game::new(signed(), -17);
+    assert_eq!(game.parse_guess("-20"), Ok(-20));
+    assert_eq!(
+        game.parse_guess("-51"),
+        Err(GuessError::OutOfRange { min: -50, max: 50 })
+    );
+    assert_eq!(game.guess(-20), Ok(Ordering::Less));
+    assert_eq!(game.guess(0), Ok(Ordering::Greater));
+    assert_eq!(game.guess(-17), Ok(Ordering::Equal));
+    assert_eq!(game.state(), State::Won);
+}
+
+#[test]
+fn huge_signed_numbers_are_compared_exactly() {
+    let settings = Settings {
+        min: i64::MAX - 10,
+        max: i64::MAX,
+        ..signed()
+    };
+    // As f64, i64::MAX - 1 and i64::MAX are the same number.
+    assert_eq!((i64::MAX - 1) as f64, i64::MAX as f64);
+    assert!(!(i64::MAX - 1).within(i64::MAX, 0));
+    assert!((i64::MAX - 1).within(i64::MAX, 1));
+    assert!(!i64::MIN.within(i64::MAX, i64::MAX));
+
+    let mut game = Game::new(settings, i64::MAX - 1);
+    assert_eq!(game.guess(i64::MAX), Ok(Ordering::Greater));
+    assert_eq!(game.guess(i64::MAX - 2), Ok(Ordering::Less));
+    assert_eq!(game.guess(i64::MAX - 1), Ok(Ordering::Equal));
+    assert_eq!(game.state(), State::Won);
+}
+
+#[test]
+fn decimals_win_within_the_tolerance() {
+    let settings = decimal();
+    assert_eq!(settings.tolerance, 0.5);
+
+    let mut game = Game::new(settings, 37.25);
+    assert_eq!(game.guess(36.5), Ok(Ordering::Less));
+    assert_eq!(game.guess(37.9), Ok(Ordering::Greater));
+    assert_eq!(game.guess(37.5), Ok(Ordering::Equal));
+    assert_eq!(game.state(), State::Won);
+}
+
+#[test]
+fn decimals_reject_what_nobody_can_guess() {
+    assert_eq!(f64::parse("2.5"), Ok(2.5));
+    assert_eq!(f64::parse("NaN"), Err(ParseError::NotANumber));
+    assert_eq!(f64::parse("inf"), Err(ParseError::NotANumber));
+}
+
+#[test]
+fn random_secrets_stay_in_range() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..100 {
+        let secret = Game::random(decimal(), &mut rng).secret();
+        assert!((1.0..=100.0).contains(&secret));
+        // Two decimal places at most.
+        assert_eq!((secret * 100.0).round() / 100.0, secret);
+
+        let secret = Game::random(signed(), &mut rng).secret();
+        assert!((-50..=50).contains(&secret));
+    }
+}
+
+#[test]
+fn a_negative_tolerance_is_invalid() {
+    let settings = Settings {
+        tolerance: -1.0,
+        ..decimal()
+    };
+
+    assert!(settings.validate().is_err());
+}
@@ -0,0 +1,56 @@
+use std::cmp::Ordering;
+
+use guessing_game::reverse::{self, Outcome};
+use guessing_game::{Difficulty, Settings};
+
+fn script_with(settings: &Settings, input: &str) -> (Outcome, String) {
+    let mut output = Vec::new();
+    let outcome = reverse::play(settings, &mut input.as_bytes(), &mut output).unwrap();
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+fn script(input: &str) -> (Outcome, String) {
+    script_with(&Difficulty::Normal.settings(), input)
+}
+
+#[test]
+fn answers_use_the_ordering_of_the_guess() {
+    assert_eq!(reverse::parse_answer("Higher"), Some(Ordering::Less));
+    assert_eq!(reverse::parse_answer(" l \n"), Some(Ordering::Greater));
+    assert_eq!(reverse::parse_answer("correct"), Some(Ordering::Equal));
+    assert_eq!(reverse::parse_answer("maybe"), None);
+}
+
+#[test]
+fn computer_finds_the_number() {
+    // Thinking of 56: 50 is too small, 75 too big, 62 too big, then 56.
+    let (outcome, transcript) = script("higher\nwhat?\nlower\nlower\ncorrect\n");
+
+    assert_eq!(outcome, Outcome::Found { guesses: 4 });
+    assert!(transcript.contains("Please answer higher, lower or correct."));
+    assert!(transcript.contains("Your number is 56."));
+}
+
+#[test]
+fn computer_can_run_out_of_lives() {
+    let (outcome, _) = script("h\nh\nh\nh\nh\n");
+
+    assert_eq!(outcome, Outcome::OutOfLives);
+}
+
+#[test]
+fn contradictions_are_called_out() {
+    let mut settings = Difficulty::Normal.settings();
+    settings.max = 3;
+
+    // Higher than 2 leaves only 3, and then 3 is too big.
+    let (outcome, transcript) = script_with(&settings, "h\nl\n");
+    assert_eq!(outcome, Outcome::Cheated);
+    assert!(
+        transcript.contains("Cheater! You said your number is higher than 2 and lower than 3.")
+    );
+
+    let (outcome, transcript) = script_with(&settings, "h\nh\n");
+    assert_eq!(outcome, Outcome::Cheated);
+    assert!(transcript.contains("higher than 3, but it is at most 3."));
+}
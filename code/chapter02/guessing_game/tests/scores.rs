@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::PathBuf;
+
+use guessing_game::scores::{self, Score};
+use guessing_game::{Difficulty, Game};
+
+/// A score file path unique to this test, inside the system temp folder.
+fn temp_file(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("guessing_game-{}-{test}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("nested").join("scores.tsv")
+}
+
+fn score(name: &str, won: bool, guesses: u32, date: &str) -> Score {
+    Score {
+        name: name.to_string(),
+        difficulty: Difficulty::Normal,
+        won,
+        guesses,
+        date: date.to_string(),
+    }
+}
+
+#[test]
+fn missing_file_has_no_scores() {
+    assert_eq!(scores::load(&temp_file("missing")).unwrap(), Vec::new());
+}
+
+#[test]
+fn appended_scores_load_back() {
+    let path = temp_file("round_trip");
+    let first = score("Ada", true, 4, "2025-01-02");
+    let second = score("Bo\tby", false, 5, "2025-01-03");
+
+    scores::append(&path, &first).unwrap();
+    scores::append(&path, &second).unwrap();
+
+    let loaded = scores::load(&path).unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0], first);
+    assert_eq!(loaded[1].name, "Bo by");
+}
+
+#[test]
+fn broken_lines_are_reported() {
+    let path = temp_file("broken");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "# comment\nAda\tnormal\twon\tfour\t2025-01-02\n").unwrap();
+
+    let error = scores::load(&path).unwrap_err();
+    assert!(error.to_string().contains(":2:"));
+}
+
+#[test]
+fn ranking_puts_fewest_guesses_first() {
+    let mut all = vec![
+        score("loser", false, 1, "2025-01-01"),
+        score("slow", true, 6, "2025-01-01"),
+        score("later", true, 3, "2025-02-01"),
+        score("fast", true, 3, "2025-01-01"),
+    ];
+
+    scores::rank(&mut all);
+
+    let names: Vec<&str> = all.iter().map(|score| score.name.as_str()).collect();
+    assert_eq!(names, ["fast", "later", "slow", "loser"]);
+}
+
+#[test]
+fn score_from_a_finished_game() {
+    let mut game = Game::new(Difficulty::Hard.settings(), 500);
+    game.guess(250).unwrap();
+    game.guess(500).unwrap();
+
+    let score = Score::from_game("Ada", &game);
+
+    assert!(score.won);
+    assert_eq!(score.guesses, 2);
+    assert_eq!(score.difficulty, Difficulty::Hard);
+    assert_eq!(score.date.len(), "YYYY-MM-DD".len());
+}
+
+#[test]
+fn leaderboard_lists_every_score() {
+    let mut output = Vec::new();
+    scores::write_leaderboard(&mut output, &[score("Ada", true, 4, "2025-01-02")]).unwrap();
+
+    let table = String::from_utf8(output).unwrap();
+    assert!(table.lines().nth(1).unwrap().contains("Ada"));
+}
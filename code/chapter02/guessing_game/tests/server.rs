@@ -0,0 +1,217 @@
+use std::io::{BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use guessing_game::Difficulty;
+use guessing_game::server::{self, MAX_GAMES, Request, Server};
+
+fn server() -> Server<StdRng> {
+    Server::new(Difficulty::Normal.settings(), StdRng::seed_from_u64(1))
+}
+
+#[test]
+fn a_new_game_starts_with_full_lives() {
+    let mut server = server();
+    let response = server.handle(&Request::new("POST", "/api/games", ""));
+
+    assert_eq!(response.status, 201);
+    assert_eq!(
+        response.body,
+        r#"{"id": 1, "state": "playing", "min": 1, "max": 100, "lives": 5, "guesses": []}"#
+    );
+}
+
+#[test]
+fn guesses_are_judged_and_bad_ones_are_free() {
+    let mut server = server();
+    server.handle(&Request::new("POST", "/api/games", ""));
+
+    let response = server.handle(&Request::new("POST", "/api/games/1/guesses", "500"));
+    assert_eq!(response.status, 422);
+    assert!(response.body.contains(r#""lives": 5"#));
+
+    let response = server.handle(&Request::new("POST", "/api/games/1/guesses", " 1\n"));
+    assert_eq!(response.status, 200);
+    assert!(response.body.contains(r#""lives": 4, "guesses": [1]"#));
+    assert!(response.body.contains(r#""result": "too small""#));
+
+    let state = server.handle(&Request::new("GET", "/api/games/1", ""));
+    assert_eq!(state.status, 200);
+    assert!(state.body.contains(r#""guesses": [1]"#));
+}
+
+#[test]
+fn the_secret_is_only_shown_when_the_game_is_over() {
+    let mut server = server();
+    server.handle(&Request::new("POST", "/api/games", ""));
+
+    let mut last = String::new();
+    for guess in 1..=5 {
+        let body = guess.to_string();
+        last = server
+            .handle(&Request::new("POST", "/api/games/1/guesses", &body))
+            .body;
+        if guess < 5 {
+            assert!(!last.contains("secret"));
+        }
+    }
+    assert!(last.contains(r#""state": "lost""#) || last.contains(r#""state": "won""#));
+    assert!(last.contains(r#""secret": "#));
+}
+
+#[test]
+fn a_guess_after_the_time_limit_shows_the_lost_game() {
+    let mut settings = Difficulty::Normal.settings();
+    settings.time_limit = Some(Duration::from_millis(1));
+    let mut server = Server::new(settings, StdRng::seed_from_u64(1));
+    server.handle(&Request::new("POST", "/api/games", ""));
+    thread::sleep(Duration::from_millis(10));
+
+    // The page shows the game in the answer, so it has to say how it ended.
+    let response = server.handle(&Request::new("POST", "/api/games/1/guesses", "50"));
+    assert_eq!(response.status, 422);
+    assert!(response.body.contains(r#""state": "lost""#));
+    assert!(response.body.contains(r#""secret": "#));
+}
+
+#[test]
+fn unknown_games_and_paths_are_errors() {
+    let mut server = server();
+
+    assert_eq!(
+        server
+            .handle(&Request::new("GET", "/api/games/1", ""))
+            .status,
+        404
+    );
+    assert_eq!(
+        server
+            .handle(&Request::new("GET", "/api/games/x", ""))
+            .status,
+        404
+    );
+    assert_eq!(
+        server.handle(&Request::new("GET", "/nothing", "")).status,
+        404
+    );
+    assert_eq!(
+        server
+            .handle(&Request::new("DELETE", "/api/games", ""))
+            .status,
+        405
+    );
+    assert_eq!(
+        server.handle(&Request::new("GET", "/", "")).content_type,
+        "text/html; charset=utf-8"
+    );
+}
+
+#[test]
+fn requests_are_read_with_their_body() {
+    let text =
+        "POST /api/games/1/guesses?debug=1 HTTP/1.1\r\nHost: x\r\ncontent-length: 2\r\n\r\n42";
+    let request = Request::read(&mut BufReader::new(text.as_bytes())).unwrap();
+
+    assert_eq!(request, Request::new("POST", "/api/games/1/guesses", "42"));
+    assert!(Request::read(&mut BufReader::new("\r\n".as_bytes())).is_err());
+}
+
+#[test]
+fn endless_lines_and_headers_are_refused() {
+    let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(10_000));
+    assert!(Request::read(&mut BufReader::new(long_line.as_bytes())).is_err());
+
+    let many_headers = format!("GET / HTTP/1.1\r\n{}\r\n", "X: y\r\n".repeat(1_000));
+    assert!(Request::read(&mut BufReader::new(many_headers.as_bytes())).is_err());
+}
+
+#[test]
+fn old_finished_games_are_forgotten_first() {
+    let mut server = server();
+    let create = Request::new("POST", "/api/games", "");
+    for _ in 0..MAX_GAMES {
+        server.handle(&create);
+    }
+    // Game 2 loses all its lives; game 1 is still being played.
+    for guess in 1..=5 {
+        let body = if guess == 5 {
+            "100"
+        } else {
+            &guess.to_string()
+        };
+        server.handle(&Request::new("POST", "/api/games/2/guesses", body));
+    }
+
+    let newest = server.handle(&create);
+    assert!(newest.body.contains(&format!("\"id\": {}", MAX_GAMES + 1)));
+    let status = |server: &mut Server<_>, id: usize| {
+        let path = format!("/api/games/{id}");
+        server.handle(&Request::new("GET", &path, "")).status
+    };
+    assert_eq!(status(&mut server, 2), 404);
+    assert_eq!(status(&mut server, 1), 200);
+
+    // With no finished game left, the oldest one goes.
+    server.handle(&create);
+    assert_eq!(status(&mut server, 1), 404);
+}
+
+/// Sends `request` to `address` and returns the whole raw response.
+fn send(address: &str, request: &str) -> String {
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn a_local_client_can_play_over_http() {
+    // Port 0 asks the system for any free port.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let server = Mutex::new(server());
+        for stream in listener.incoming().take(2) {
+            server::answer(&server, stream.unwrap()).unwrap();
+        }
+    });
+
+    let created = send(
+        &address,
+        "POST /api/games HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+    assert!(created.starts_with("HTTP/1.1 201 Created\r\n"));
+    assert!(created.contains("Content-Type: application/json\r\n"));
+    assert!(created.ends_with(r#""guesses": []}"#));
+
+    let guessed = send(
+        &address,
+        "POST /api/games/1/guesses HTTP/1.1\r\nContent-Length: 2\r\n\r\n50",
+    );
+    assert!(guessed.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(guessed.contains(r#""guesses": [50]"#));
+
+    server.join().unwrap();
+}
+
+#[test]
+fn an_idle_connection_does_not_hold_up_the_others() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || server().serve(&listener));
+
+    // Like a browser opening a connection in advance and sending nothing on it.
+    let _idle = TcpStream::connect(&address).unwrap();
+    let started = std::time::Instant::now();
+    let created = send(&address, "POST /api/games HTTP/1.1\r\n\r\n");
+
+    assert!(created.starts_with("HTTP/1.1 201 Created\r\n"));
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+}
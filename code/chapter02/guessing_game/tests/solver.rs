@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use guessing_game::solver::{self, Benchmark, Solver};
+use guessing_game::{Difficulty, Game, State};
+
+#[test]
+fn solver_guesses_the_middle() {
+    let mut solver = Solver::new(1, 100);
+    assert_eq!(solver.next_guess(), Some(50));
+
+    solver.learn(50, Ordering::Greater);
+    assert_eq!(solver.remaining(), Some((1, 49)));
+
+    solver.learn(25, Ordering::Less);
+    assert_eq!(solver.remaining(), Some((26, 49)));
+}
+
+#[test]
+fn contradicting_answers_leave_nothing() {
+    let mut solver = Solver::new(1, 100);
+    solver.learn(50, Ordering::Less);
+    solver.learn(51, Ordering::Greater);
+
+    assert_eq!(solver.remaining(), None);
+    assert_eq!(solver.next_guess(), None);
+}
+
+#[test]
+fn solver_handles_the_edges_of_u32() {
+    let mut solver = Solver::new(0, u32::MAX);
+    solver.learn(u32::MAX, Ordering::Less);
+    assert_eq!(solver.remaining(), None);
+
+    let mut solver = Solver::new(0, u32::MAX);
+    solver.learn(0, Ordering::Greater);
+    assert_eq!(solver.remaining(), None);
+}
+
+#[test]
+fn every_secret_is_found_within_the_worst_case() {
+    let worst = solver::worst_case(1, 100);
+    assert_eq!(worst, 7);
+
+    for secret in 1..=100 {
+        let needed = solver::guesses_needed(1, 100, secret);
+        assert!(needed <= worst, "{secret} took {needed} guesses");
+    }
+}
+
+#[test]
+fn auto_play_wins_with_enough_lives() {
+    let mut settings = Difficulty::Normal.settings();
+    settings.lives = 7;
+    let mut game = Game::new(settings, 73);
+    let mut output = Vec::new();
+
+    assert_eq!(
+        solver::auto_play(&mut game, &mut output).unwrap(),
+        State::Won
+    );
+    assert!(
+        String::from_utf8(output)
+            .unwrap()
+            .contains("The bot won in")
+    );
+}
+
+#[test]
+fn benchmark_counts_wins_within_the_budget() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let benchmark = Benchmark::run(Difficulty::Normal.settings(), 500, &mut rng);
+
+    assert_eq!(benchmark.games(), 500);
+    assert!(benchmark.worst() <= 7);
+    let within_budget: u32 = (1..=5).filter_map(|n| benchmark.counts.get(&n)).sum();
+    assert_eq!(benchmark.wins(), within_budget);
+}
@@ -0,0 +1,114 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::thread;
+use std::time::Duration;
+
+use guessing_game::timed::{self, Line, TimedInput};
+use guessing_game::{Difficulty, Game, State};
+
+/// A reader that never produces anything, like a player who walked away.
+struct Silent;
+
+impl Read for Silent {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        thread::sleep(Duration::from_secs(3600));
+        Ok(0)
+    }
+}
+
+fn timed_game(turn_limit: Duration) -> Game {
+    let mut settings = Difficulty::Normal.settings();
+    settings.lives = 2;
+    settings.turn_limit = Some(turn_limit);
+    Game::new(settings, 42)
+}
+
+#[test]
+fn lines_arrive_then_the_input_closes() {
+    let mut input = TimedInput::spawn("50\n".as_bytes());
+
+    assert_eq!(
+        input.next_line(None).unwrap(),
+        Line::Text(String::from("50\n"))
+    );
+    assert_eq!(input.next_line(None).unwrap(), Line::Closed);
+}
+
+#[test]
+fn waiting_gives_up_after_the_timeout() {
+    let mut input = TimedInput::spawn(BufReader::new(Silent));
+
+    let line = input.next_line(Some(Duration::from_millis(20))).unwrap();
+
+    assert_eq!(line, Line::TimedOut);
+}
+
+#[test]
+fn slow_guesses_cost_lives() {
+    let mut game = timed_game(Duration::from_millis(20));
+    let mut input = TimedInput::spawn(BufReader::new(Silent));
+    let mut output = Vec::new();
+
+    let state = timed::play(&mut game, &mut input, &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(state, State::Lost);
+    assert!(game.guesses().is_empty());
+    assert_eq!(output.matches("Too slow! That cost a life.").count(), 2);
+}
+
+#[test]
+fn quick_guesses_play_normally() {
+    let mut game = timed_game(Duration::from_secs(5));
+    let mut input = TimedInput::spawn("50\n42\n".as_bytes());
+    let mut output = Vec::new();
+
+    let state = timed::play(&mut game, &mut input, &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(state, State::Won);
+    assert!(output.contains("5 seconds for this guess"));
+    assert!(output.contains("Too big!"));
+}
+
+#[test]
+fn closed_input_abandons_the_game() {
+    let mut game = timed_game(Duration::from_secs(5));
+    let mut input = TimedInput::spawn("50\n".as_bytes());
+    let mut output = Vec::new();
+
+    let state = timed::play(&mut game, &mut input, &mut output).unwrap();
+
+    assert_eq!(state, State::Abandoned);
+    assert_eq!(game.guesses(), &[50]);
+    assert!(String::from_utf8(output).unwrap().contains("No more input"));
+}
+
+#[test]
+fn the_game_clock_ends_the_game() {
+    let mut game = timed_game(Duration::from_secs(5));
+    let mut settings = game.settings().clone();
+    settings.time_limit = Some(Duration::from_millis(20));
+    game = Game::new(settings, 42);
+    let mut input = TimedInput::spawn(BufReader::new(Silent));
+    let mut output = Vec::new();
+
+    let state = timed::play(&mut game, &mut input, &mut output).unwrap();
+
+    assert_eq!(state, State::Lost);
+    assert!(String::from_utf8(output).unwrap().contains("Time's up!"));
+    assert_eq!(game.lives(), 2);
+}
+
+#[test]
+fn it_reads_like_any_other_buf_read() {
+    let mut input = TimedInput::spawn("y\n42\n".as_bytes());
+    let mut line = String::new();
+
+    input.read_line(&mut line).unwrap();
+    assert_eq!(line, "y\n");
+    assert_eq!(
+        input.next_line(None).unwrap(),
+        Line::Text(String::from("42\n"))
+    );
+    assert_eq!(input.fill_buf().unwrap(), b"");
+}
@@ -0,0 +1,57 @@
+use guessing_game::play::play_again;
+use guessing_game::tournament::Scoreboard;
+use guessing_game::{Difficulty, Game};
+
+fn finished_game(secret: u32, guesses: &[u32]) -> Game {
+    let mut game = Game::new(Difficulty::Normal.settings(), secret);
+    for &guess in guesses {
+        game.guess(guess).unwrap();
+    }
+    game
+}
+
+fn ask(input: &str) -> (bool, String) {
+    let mut output = Vec::new();
+    let again = play_again(&mut input.as_bytes(), &mut output).unwrap();
+    (again, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn scoreboard_tallies_rounds() {
+    let mut scoreboard = Scoreboard::default();
+    assert_eq!(scoreboard.average_guesses(), None);
+
+    scoreboard.record(&finished_game(30, &[50, 25, 30]));
+    scoreboard.record(&finished_game(30, &[1, 2, 3, 4, 5]));
+
+    assert_eq!(scoreboard.rounds().len(), 2);
+    assert_eq!((scoreboard.wins(), scoreboard.losses()), (1, 1));
+    assert_eq!(scoreboard.average_guesses(), Some(4.0));
+
+    let text = scoreboard.to_string();
+    assert!(text.contains("Round  1  won    3 guesses  (the number was 30)"));
+    assert!(text.contains("1 won, 1 lost out of 2 rounds."));
+    assert!(text.contains("4.0 guesses per round on average."));
+}
+
+#[test]
+fn play_again_reads_yes_and_no() {
+    assert!(ask("y\n").0);
+    assert!(ask("YES\n").0);
+    assert!(!ask("n\n").0);
+    // Just pressing Enter takes the default, no.
+    assert!(!ask("\n").0);
+}
+
+#[test]
+fn play_again_asks_until_it_understands() {
+    let (again, output) = ask("maybe\ny\n");
+
+    assert!(again);
+    assert!(output.contains("Please answer y or n."));
+}
+
+#[test]
+fn play_again_stops_at_the_end_of_input() {
+    assert!(!ask("").0);
+}
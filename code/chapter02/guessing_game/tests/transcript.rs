@@ -0,0 +1,90 @@
+use std::cmp::Ordering;
+use std::path::Path;
+
+use guessing_game::transcript::{self, Format, Summary};
+use guessing_game::{Difficulty, Game};
+
+/// A won game over 1..=100 with the secret 14: 50, 25, 12, then 14.
+fn finished_game() -> Game {
+    let mut game = Game::new(Difficulty::Normal.settings(), 14);
+    for guess in [50, 25, 12, 14] {
+        game.guess(guess).unwrap();
+    }
+    game
+}
+
+#[test]
+fn game_records_every_turn() {
+    let game = finished_game();
+    let turns = game.turns();
+
+    assert_eq!(turns.len(), 4);
+    assert_eq!(turns[0].ordering, Some(Ordering::Greater));
+    assert_eq!(turns[2].ordering, Some(Ordering::Less));
+    assert_eq!(turns[3].lives, 2);
+    assert!(
+        turns
+            .windows(2)
+            .all(|pair| pair[0].elapsed <= pair[1].elapsed)
+    );
+    assert!(game.elapsed() >= turns[3].elapsed);
+}
+
+#[test]
+fn summary_compares_with_binary_search() {
+    let summary = Summary::new(&finished_game());
+
+    assert!(summary.won);
+    assert_eq!(summary.guesses, 4);
+    assert_eq!(summary.optimal_worst_case, 7);
+    assert_eq!(summary.optimal_for_secret, 7);
+    assert!(summary.to_string().contains("Won after 4 guesses"));
+}
+
+#[test]
+fn format_comes_from_the_extension() {
+    assert_eq!(
+        Format::from_path(Path::new("a/game.JSON")),
+        Ok(Format::Json)
+    );
+    assert_eq!(Format::from_path(Path::new("game.csv")), Ok(Format::Csv));
+    assert!(Format::from_path(Path::new("game.txt")).is_err());
+    assert!(Format::from_path(Path::new("game")).is_err());
+}
+
+#[test]
+fn csv_has_one_row_per_guess() {
+    let mut output = Vec::new();
+    transcript::write_csv(&mut output, &finished_game()).unwrap();
+    let csv = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "turn,guess,result,elapsed_ms,lives_left");
+    assert_eq!(lines.len(), 5);
+    assert!(lines[1].starts_with("1,50,too big,"));
+    assert!(lines[4].starts_with("4,14,correct,"));
+    assert!(lines[4].ends_with(",2"));
+}
+
+#[test]
+fn json_lists_the_rules_and_guesses() {
+    let mut output = Vec::new();
+    transcript::write_json(&mut output, &finished_game(), "Ada").unwrap();
+    let json = String::from_utf8(output).unwrap();
+
+    assert!(json.starts_with("{\n  \"player\": \"Ada\",\n"));
+    assert!(json.contains("\"secret\": 14,"));
+    assert!(json.contains("\"won\": true,"));
+    assert_eq!(json.matches("\"turn\":").count(), 4);
+    assert!(json.contains("\"lives_left\": 2}\n  ]\n}\n"));
+}
+
+#[test]
+fn json_strings_are_escaped() {
+    assert_eq!(transcript::json_string("Ada"), "\"Ada\"");
+    assert_eq!(
+        transcript::json_string("say \"hi\"\\\n"),
+        "\"say \\\"hi\\\"\\\\\\n\""
+    );
+    assert_eq!(transcript::json_string("\u{7}"), "\"\\u0007\"");
+}
@@ -0,0 +1,80 @@
+use std::io::{self, BufReader, Read};
+use std::thread;
+use std::time::Duration;
+
+use guessing_game::timed::TimedInput;
+use guessing_game::tui::{self, hearts, range_bar};
+use guessing_game::{Difficulty, Game, State};
+
+/// A reader that never produces anything, like a player who walked away.
+struct Silent;
+
+impl Read for Silent {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        thread::sleep(Duration::from_secs(3600));
+        Ok(0)
+    }
+}
+
+#[test]
+fn hearts_show_lives_left_and_lost() {
+    assert_eq!(hearts(3, 5), "♥♥♥♡♡");
+    assert_eq!(hearts(0, 2), "♡♡");
+}
+
+#[test]
+fn range_bar_fills_the_possible_part() {
+    let bar = range_bar(1, 100, Some((51, 100)));
+
+    assert_eq!(bar.chars().count(), 50);
+    assert!(bar.starts_with("·························█"));
+    assert!(bar.ends_with('█'));
+    assert!(!range_bar(1, 100, None).contains('█'));
+}
+
+#[test]
+fn screen_shows_history_and_narrowed_range() {
+    let mut game = Game::new(Difficulty::Normal.settings(), 30);
+    game.guess(50).unwrap();
+    game.guess(25).unwrap();
+
+    let screen = tui::render(&game, "Too small!");
+
+    assert!(screen.contains("Lives  ♥♥♥♡♡\n"));
+    assert!(screen.contains("26..=49"));
+    assert!(screen.contains("50  too big\n"));
+    assert!(screen.contains("25  too small\n"));
+    assert!(screen.ends_with("Too small!\n"));
+}
+
+#[test]
+fn screen_is_redrawn_after_every_guess() {
+    let mut game = Game::new(Difficulty::Normal.settings(), 30);
+    let mut output = Vec::new();
+
+    let state = tui::play(&mut game, &mut "50\n30\n".as_bytes(), &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(state, State::Won);
+    assert_eq!(output.matches("\x1b[2J").count(), 3);
+    assert!(output.ends_with("You win!\n"));
+}
+
+#[test]
+fn slow_guesses_cost_lives_on_the_full_screen_too() {
+    let mut settings = Difficulty::Normal.settings();
+    settings.lives = 2;
+    settings.turn_limit = Some(Duration::from_millis(20));
+    let mut game = Game::new(settings, 42);
+    let mut input = TimedInput::spawn(BufReader::new(Silent));
+    let mut output = Vec::new();
+
+    let state = tui::play_timed(&mut game, &mut input, &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(state, State::Lost);
+    assert_eq!(game.turns().len(), 2);
+    assert!(game.turns().iter().all(|turn| turn.timed_out()));
+    assert!(output.contains("[0s] > "));
+    assert!(output.contains("Too slow! That cost a life."));
+}
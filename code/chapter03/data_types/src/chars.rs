@@ -0,0 +1,270 @@
+/*
+ * The char inspector.
+ *
+ * A Rust `char` is one Unicode scalar value (a code point, U+0000 to U+10FFFF
+ * without the surrogates), always 4 bytes in memory. A `String` stores chars
+ * as UTF-8, 1 to 4 bytes each; Windows and JavaScript use UTF-16, 1 or 2 units each.
+ *
+ * What a reader sees as one character can still be several chars:
+ * 'é' may be 'e' followed by U+0301 COMBINING ACUTE ACCENT, and 👍🏽 is a thumb
+ * followed by a skin tone modifier. Those "grapheme clusters" come from the
+ * unicode-segmentation crate; `text.chars().count()` knows nothing about them.
+ */
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use unicode_general_category::get_general_category;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Everything about one char.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharInfo {
+    pub ch: char,
+    /// Where the char starts in the UTF-8 text, the index `&text[offset..]` would use.
+    pub offset: usize,
+    pub utf8: Vec<u8>,
+    pub utf16: Vec<u16>,
+    /// The general category's short name, like "Lu" or "Mn".
+    pub category: &'static str,
+    /// The same in words, like "uppercase letter".
+    pub category_name: String,
+    pub alphabetic: bool,
+    pub numeric: bool,
+    pub whitespace: bool,
+}
+
+impl CharInfo {
+    pub fn new(ch: char, offset: usize) -> Self {
+        let mut utf8 = [0; 4];
+        let mut utf16 = [0; 2];
+        let category = get_general_category(ch);
+
+        Self {
+            ch,
+            offset,
+            utf8: ch.encode_utf8(&mut utf8).as_bytes().to_vec(),
+            utf16: ch.encode_utf16(&mut utf16).to_vec(),
+            category: category.abbreviation(),
+            category_name: words(&format!("{category:?}")),
+            alphabetic: ch.is_alphabetic(),
+            numeric: ch.is_numeric(),
+            whitespace: ch.is_whitespace(),
+        }
+    }
+
+    /// Like `U+00E9`: at least 4 hex digits, as Unicode writes them.
+    pub fn code_point(&self) -> String {
+        format!("U+{:04X}", u32::from(self.ch))
+    }
+
+    /// The char as it can be shown in a terminal: controls, marks and invisible
+    /// chars are escaped the way `{:?}` would, like `\n` or `\u{301}`.
+    pub fn printable(&self) -> String {
+        self.ch.escape_debug().to_string()
+    }
+}
+
+/// `LowercaseLetter` as "lowercase letter".
+fn words(name: &str) -> String {
+    let mut words = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !words.is_empty() {
+            words.push(' ');
+        }
+        words.push(c.to_ascii_lowercase());
+    }
+    words
+}
+
+/// One grapheme cluster: what a reader counts as one character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    pub text: String,
+    pub chars: Vec<CharInfo>,
+}
+
+/// The text, split into grapheme clusters and chars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub text: String,
+    pub clusters: Vec<Cluster>,
+}
+
+impl Report {
+    pub fn char_count(&self) -> usize {
+        self.clusters
+            .iter()
+            .map(|cluster| cluster.chars.len())
+            .sum()
+    }
+
+    pub fn utf16_len(&self) -> usize {
+        self.text.encode_utf16().count()
+    }
+
+    /// The clusters made of more than one char.
+    pub fn multi_char_clusters(&self) -> impl Iterator<Item = &Cluster> {
+        self.clusters
+            .iter()
+            .filter(|cluster| cluster.chars.len() > 1)
+    }
+}
+
+/// Splits `text` into clusters, and every cluster into chars.
+pub fn inspect(text: &str) -> Report {
+    // `true` asks for extended clusters, the ones Unicode recommends for "user-perceived characters".
+    let clusters = text
+        .grapheme_indices(true)
+        .map(|(start, cluster)| Cluster {
+            text: cluster.to_string(),
+            chars: cluster
+                .char_indices()
+                .map(|(offset, ch)| CharInfo::new(ch, start + offset))
+                .collect(),
+        })
+        .collect();
+
+    Report {
+        text: text.to_string(),
+        clusters,
+    }
+}
+
+/// Replaces `\u{...}` escapes with their chars, so combining marks and
+/// invisible chars can be typed: `e\u{301}` is an e with a combining accent.
+pub fn unescape(text: &str) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("\\u{") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 3..];
+        let Some(end) = after.find('}') else {
+            return Err(format!("'{}' is missing its closing brace", &rest[start..]));
+        };
+
+        let hex = &after[..end];
+        let ch = u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| format!("'\\u{{{hex}}}' is not a Unicode scalar value"))?;
+        result.push(ch);
+        rest = &after[end + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|byte| format!("{byte:02X}")).collect();
+    bytes.join(" ")
+}
+
+fn hex_units(units: &[u16]) -> String {
+    let units: Vec<String> = units.iter().map(|unit| format!("{unit:04X}")).collect();
+    units.join(" ")
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?} is {} grapheme clusters, {} chars, {} UTF-8 bytes and {} UTF-16 units.",
+            self.text,
+            self.clusters.len(),
+            self.char_count(),
+            self.text.len(),
+            self.utf16_len()
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "    {:<6}  {:<9}  {:<11}  {:<9}  {:<25}  {:<20}  char",
+            "offset", "code", "UTF-8", "UTF-16", "category", "properties"
+        )?;
+
+        for cluster in &self.clusters {
+            /*
+             * A cluster of several chars gets a heading, and its chars a bar in front,
+             * so it's easy to see which chars a reader would count as one.
+             */
+            let bar = if cluster.chars.len() > 1 {
+                writeln!(
+                    f,
+                    "  ┌ {:?} is one grapheme of {} chars",
+                    cluster.text,
+                    cluster.chars.len()
+                )?;
+                "│ "
+            } else {
+                "  "
+            };
+
+            for info in &cluster.chars {
+                let flags: Vec<&str> = [
+                    (info.alphabetic, "alphabetic"),
+                    (info.numeric, "numeric"),
+                    (info.whitespace, "whitespace"),
+                ]
+                .into_iter()
+                .filter_map(|(set, name)| set.then_some(name))
+                .collect();
+                let category = format!("{} {}", info.category, info.category_name);
+
+                writeln!(
+                    f,
+                    "  {bar}{:<6}  {:<9}  {:<11}  {:<9}  {:<25}  {:<20}  {}",
+                    info.offset,
+                    info.code_point(),
+                    hex_bytes(&info.utf8),
+                    hex_units(&info.utf16),
+                    category,
+                    flags.join(" "),
+                    info.printable()
+                )?;
+            }
+        }
+
+        let multi = self.multi_char_clusters().count();
+        if multi > 0 {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{multi} grapheme cluster(s) above are several chars: .chars() and .len() count them more than once,"
+            )?;
+            writeln!(
+                f,
+                "and slicing or truncating inside one splits the accent or emoji from its base."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Inspects text line by line until an empty line or the end of input.
+pub fn run<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<()> {
+    writeln!(
+        output,
+        "Type some text, like `héllo`, `👍🏽` or `e\\u{{301}}` (\\u{{...}} types any code point)."
+    )?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.is_empty() {
+            return Ok(());
+        }
+
+        match unescape(line) {
+            Ok(text) => writeln!(output, "\n{}", inspect(&text))?,
+            Err(message) => writeln!(output, "{message}")?,
+        }
+    }
+}
@@ -0,0 +1,392 @@
+/*
+ * The IEEE-754 float inspector.
+ *
+ * An f64 is 64 bits split into three fields (an f32 is 32 bits: 1, 8 and 23):
+ *
+ *     sign (1 bit) | exponent (11 bits) | mantissa (52 bits)
+ *
+ * and a normal number means (-1)^sign × 1.mantissa × 2^(exponent - 1023).
+ * Only numbers of that shape can be stored, and 0.1 isn't one of them,
+ * so `0.1` is rounded to the nearest one: 0.1000000000000000055511151231257827...
+ * That is why 0.1 + 0.2 != 0.3 — try `cargo run -- float 0.1 + 0.2`.
+ */
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::num::FpCategory;
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
+/// The two float types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatType {
+    F32,
+    F64,
+}
+
+impl fmt::Display for FloatType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FloatType::F32 => "f32",
+            FloatType::F64 => "f64",
+        })
+    }
+}
+
+/*
+ * Like `Integer` in overflow.rs: one trait with what the inspector needs,
+ * implemented for both float types by a macro.
+ */
+trait Float:
+    Copy
+    + fmt::Debug
+    + fmt::Display
+    + fmt::LowerExp
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const TYPE: FloatType;
+    const EXPONENT_BITS: u32;
+    const MANTISSA_BITS: u32;
+
+    /// The raw bits, widened to a u64 so both types can share the code.
+    fn bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+    fn classify(self) -> FpCategory;
+    fn next_up(self) -> Self;
+    fn next_down(self) -> Self;
+}
+
+macro_rules! float {
+    ($($t:ty => $ty:expr, $exponent:expr, $mantissa:expr);*) => {$(
+        impl Float for $t {
+            const TYPE: FloatType = $ty;
+            const EXPONENT_BITS: u32 = $exponent;
+            const MANTISSA_BITS: u32 = $mantissa;
+
+            fn bits(self) -> u64 {
+                u64::from(self.to_bits())
+            }
+
+            fn from_bits(bits: u64) -> Self {
+                // The caller already checked the bits fit, so `as` only drops zeros.
+                <$t>::from_bits(bits as _)
+            }
+
+            fn classify(self) -> FpCategory {
+                <$t>::classify(self)
+            }
+
+            fn next_up(self) -> Self {
+                <$t>::next_up(self)
+            }
+
+            fn next_down(self) -> Self {
+                <$t>::next_down(self)
+            }
+        }
+    )*};
+}
+
+float!(f32 => FloatType::F32, 8, 23; f64 => FloatType::F64, 11, 52);
+
+/// An arbitrarily big whole number, just enough to print floats exactly.
+///
+/// The digits are kept in base 1_000_000_000 ("limbs" of 9 decimal digits), lowest first.
+struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    const BASE: u64 = 1_000_000_000;
+
+    fn new(value: u64) -> Self {
+        let mut limbs = vec![(value % Self::BASE) as u32];
+        let mut rest = value / Self::BASE;
+        while rest > 0 {
+            limbs.push((rest % Self::BASE) as u32);
+            rest /= Self::BASE;
+        }
+        Self { limbs }
+    }
+
+    /// Multiplies in place, the way it's done on paper: limb by limb with a carry.
+    fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0;
+        for limb in &mut self.limbs {
+            let product = u64::from(*limb) * u64::from(factor) + carry;
+            *limb = (product % Self::BASE) as u32;
+            carry = product / Self::BASE;
+        }
+        while carry > 0 {
+            self.limbs.push((carry % Self::BASE) as u32);
+            carry /= Self::BASE;
+        }
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut limbs = self.limbs.iter().rev();
+        if let Some(first) = limbs.next() {
+            write!(f, "{first}")?;
+        }
+        // Every limb but the first is padded to its full 9 digits.
+        for limb in limbs {
+            write!(f, "{limb:09}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `significand × 2^exponent` written out in decimal, every digit of it.
+///
+/// A negative power of two has a finite decimal expansion: 2^-k = 5^k / 10^k.
+pub fn exact_decimal(significand: u64, exponent: i32) -> String {
+    let mut number = BigUint::new(significand);
+    if exponent >= 0 {
+        for _ in 0..exponent {
+            number.mul_small(2);
+        }
+        return number.to_string();
+    }
+
+    let places = exponent.unsigned_abs() as usize;
+    for _ in 0..places {
+        number.mul_small(5);
+    }
+    // `number` is now the value × 10^places: put the decimal point back in.
+    let digits = format!("{:0>width$}", number.to_string(), width = places + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - places);
+    match fraction.trim_end_matches('0') {
+        "" => whole.to_string(),
+        fraction => format!("{whole}.{fraction}"),
+    }
+}
+
+/// Everything the inspector found out about one float, ready to print.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ty: FloatType,
+    /// What was typed, like `0.1 + 0.2`.
+    pub input: String,
+    /// How each typed number was actually stored, for expressions.
+    pub operands: Vec<(String, String)>,
+    /// The shortest decimal that reads back as the same float: what `println!("{:?}")` shows.
+    pub shortest: String,
+    /// "normal", "subnormal", "zero", "infinity" or "NaN".
+    pub category: &'static str,
+    pub sign: u64,
+    pub exponent: u64,
+    pub mantissa: u64,
+    /// The bits as `sign exponent mantissa`.
+    pub bits: String,
+    /// The exponent with the bias taken off, for finite numbers.
+    pub unbiased: Option<i32>,
+    /// `1.xxx × 2^e`, for finite non-zero numbers.
+    pub formula: Option<String>,
+    /// Every digit of the stored value, for finite numbers.
+    pub exact: Option<String>,
+    pub next_down: String,
+    pub next_up: String,
+    /// The gap to the next float away from zero, for finite numbers.
+    pub ulp: Option<String>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} as {} is {}", self.input, self.ty, self.shortest)?;
+        for (text, exact) in &self.operands {
+            writeln!(f, "  ({text} is stored as {exact})")?;
+        }
+        if !self.operands.is_empty() {
+            writeln!(
+                f,
+                "  (the exact result of those is rounded again, to the nearest {})",
+                self.ty
+            )?;
+        }
+        writeln!(f)?;
+
+        let sign = if self.sign == 1 {
+            "negative"
+        } else {
+            "positive"
+        };
+        let exponent = match self.unbiased {
+            Some(unbiased) => format!("{}, that is 2^{unbiased}", self.exponent),
+            None => format!("{}, all ones: infinity or NaN", self.exponent),
+        };
+        let rows = [
+            ("category", self.category.to_string()),
+            ("bits", self.bits.clone()),
+            ("sign", format!("{} ({sign})", self.sign)),
+            ("exponent", exponent),
+            ("mantissa", format!("{:#x}", self.mantissa)),
+            ("formula", self.formula.clone().unwrap_or_default()),
+            ("exact value", self.exact.clone().unwrap_or_default()),
+            ("next down", self.next_down.clone()),
+            ("next up", self.next_up.clone()),
+            ("ULP", self.ulp.clone().unwrap_or_default()),
+        ];
+        for (name, value) in rows {
+            if !value.is_empty() {
+                writeln!(f, "  {name:<11}  {value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: Float>(text: &str) -> Result<T, String> {
+    let bits_width = 1 + T::EXPONENT_BITS + T::MANTISSA_BITS;
+    // 0x... gives the bits directly, handy for NaN payloads and the exact neighbours.
+    if let Some(hex) = text.strip_prefix("0x") {
+        let bits = u64::from_str_radix(&hex.replace('_', ""), 16)
+            .map_err(|_| format!("'{text}' is not hexadecimal bits"))?;
+        if bits_width < 64 && bits >> bits_width != 0 {
+            return Err(format!("'{text}' has more than {bits_width} bits"));
+        }
+        return Ok(T::from_bits(bits));
+    }
+
+    text.replace('_', "").parse().map_err(|_| {
+        format!("'{text}' is not a number (try 0.1, -2.5e-3, inf, NaN or 0x3fb999999999999a)")
+    })
+}
+
+fn report<T: Float>(input: String, value: T, operands: Vec<(String, String)>) -> Report {
+    let bits = value.bits();
+    let mantissa_bits = T::MANTISSA_BITS;
+    let exponent_bits = T::EXPONENT_BITS;
+    let bias = (1 << (exponent_bits - 1)) - 1;
+
+    let sign = bits >> (exponent_bits + mantissa_bits);
+    let exponent = (bits >> mantissa_bits) & ((1 << exponent_bits) - 1);
+    let mantissa = bits & ((1 << mantissa_bits) - 1);
+
+    let category = match value.classify() {
+        FpCategory::Normal => "normal",
+        FpCategory::Subnormal => "subnormal",
+        FpCategory::Zero => "zero",
+        FpCategory::Infinite => "infinity",
+        FpCategory::Nan => "NaN",
+    };
+
+    /*
+     * Subnormals (exponent field 0) have no hidden 1 and use the smallest exponent,
+     * so numbers can get closer to zero than 1.0 × 2^-1022, one bit at a time.
+     * `significand` is the 1.mantissa (or 0.mantissa) as a whole number.
+     */
+    let finite = exponent != (1 << exponent_bits) - 1;
+    let (significand, power) = if exponent == 0 {
+        (mantissa, 1 - bias)
+    } else {
+        (mantissa | (1 << mantissa_bits), exponent as i32 - bias)
+    };
+    let scale = power - mantissa_bits as i32;
+
+    let exact = finite.then(|| {
+        let digits = exact_decimal(significand, scale);
+        if sign == 1 {
+            format!("-{digits}")
+        } else {
+            digits
+        }
+    });
+    let formula = (finite && significand != 0).then(|| {
+        let sign = if sign == 1 { "-" } else { "+" };
+        let fraction = exact_decimal(significand, -(mantissa_bits as i32));
+        format!("{sign}{fraction} × 2^{power}")
+    });
+    // In two halves, because 2^1074 overflows before powi gets to divide by it.
+    let ulp = finite.then(|| {
+        let ulp = 2f64.powi(scale / 2) * 2f64.powi(scale - scale / 2);
+        format!("2^{scale} ≈ {ulp:e}")
+    });
+
+    Report {
+        ty: T::TYPE,
+        input,
+        operands,
+        shortest: format!("{value:?}"),
+        category,
+        sign,
+        exponent,
+        mantissa,
+        bits: format!(
+            "{sign} {exponent:0e$b} {mantissa:0m$b}",
+            e = exponent_bits as usize,
+            m = mantissa_bits as usize
+        ),
+        unbiased: finite.then_some(power),
+        formula,
+        exact,
+        next_down: format!("{:?}", value.next_down()),
+        next_up: format!("{:?}", value.next_up()),
+        ulp,
+    }
+}
+
+fn inspect_as<T: Float>(words: &[&str]) -> Result<Report, String> {
+    let input = words.join(" ");
+    match words {
+        [text] => Ok(report(input, parse::<T>(text)?, Vec::new())),
+        [a, op, b] => {
+            let (x, y) = (parse::<T>(a)?, parse::<T>(b)?);
+            let value = match *op {
+                "+" => x + y,
+                "-" => x - y,
+                "*" => x * y,
+                "/" => x / y,
+                other => return Err(format!("unknown operator '{other}' (expected + - * or /)")),
+            };
+            let stored = |text: &str, value: T| {
+                let digits = report(String::new(), value, Vec::new()).exact;
+                (
+                    text.to_string(),
+                    digits.unwrap_or_else(|| format!("{value:?}")),
+                )
+            };
+            Ok(report(input, value, vec![stored(a, x), stored(b, y)]))
+        }
+        _ => Err(String::from(
+            "expected a number, or two numbers and an operator, like: 0.1 + 0.2",
+        )),
+    }
+}
+
+/// Inspects `[f32|f64] <number>` or `[f32|f64] <a> <op> <b>`. Without a type, it's f64.
+pub fn inspect(words: &[&str]) -> Result<Report, String> {
+    match words {
+        ["f32", rest @ ..] => inspect_as::<f32>(rest),
+        ["f64", rest @ ..] => inspect_as::<f64>(rest),
+        _ => inspect_as::<f64>(words),
+    }
+}
+
+/// Inspects numbers line by line until an empty line or the end of input.
+pub fn run<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<()> {
+    writeln!(
+        output,
+        "Type a number or a sum, like `0.1`, `f32 16777217` or `0.1 + 0.2` (f64 unless you say f32)."
+    )?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match inspect(&words) {
+            Ok(report) => writeln!(output, "\n{report}")?,
+            Err(message) => writeln!(output, "{message}")?,
+        }
+    }
+}
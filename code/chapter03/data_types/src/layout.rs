@@ -0,0 +1,299 @@
+/*
+ * The memory layout table.
+ *
+ * Every type has a size (how many bytes a value takes) and an alignment
+ * (its address must be a multiple of it). The compiler adds padding so every
+ * field of a tuple or struct is aligned, and may reorder the fields to need less.
+ * Some types also have "niches": bit patterns a valid value never uses, like 2 in a bool
+ * or 0 in a reference, so `Option` can store `None` there without an extra tag.
+ *
+ * The numbers are measured with size_of, align_of and offset_of on this machine,
+ * so pointer-sized types show 8 bytes on a 64-bit computer and 4 on a 32-bit one.
+ * Try `cargo run -- layout`, or `cargo run -- layout --markdown` for the docs.
+ */
+use std::fmt::Display;
+use std::mem::{align_of, offset_of, size_of};
+use std::num::NonZeroU32;
+
+/// One line of the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The type as it is written in Rust, like `(i32, f64, u8)`.
+    pub ty: String,
+    pub size: usize,
+    pub align: usize,
+    /// For tuples and structs: which field owns each byte, `.` for padding.
+    pub bytes: Option<String>,
+    pub note: String,
+}
+
+impl Row {
+    pub fn new(ty: &str, size: usize, align: usize, note: &str) -> Self {
+        Self {
+            ty: ty.to_string(),
+            size,
+            align,
+            bytes: None,
+            note: note.to_string(),
+        }
+    }
+
+    /// A row for a type with fields, from each field's `(offset, size)`.
+    pub fn with_fields(ty: &str, size: usize, align: usize, fields: &[(usize, usize)]) -> Self {
+        let mut bytes = vec!['.'; size];
+        for (index, &(offset, field_size)) in fields.iter().enumerate() {
+            let mark = char::from_digit(index as u32, 36).unwrap_or('?');
+            bytes[offset..offset + field_size].fill(mark);
+        }
+
+        let padding = bytes.iter().filter(|&&byte| byte == '.').count();
+        let reordered = fields.windows(2).any(|pair| pair[0].0 > pair[1].0);
+        let mut note = format!("{padding} padding byte(s)");
+        if reordered {
+            note.push_str(", fields reordered");
+        }
+
+        // Groups of 8 bytes, like the rows of a hex dump.
+        let groups: Vec<String> = bytes
+            .chunks(8)
+            .map(|group| group.iter().collect())
+            .collect();
+
+        Self {
+            bytes: Some(groups.join(" ")),
+            ..Self::new(ty, size, align, &note)
+        }
+    }
+}
+
+/// A titled group of rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub title: &'static str,
+    pub rows: Vec<Row>,
+}
+
+/// The size and alignment of `T`.
+macro_rules! row {
+    ($t:ty, $note:expr) => {
+        Row::new(stringify!($t), size_of::<$t>(), align_of::<$t>(), $note)
+    };
+}
+
+/// A row with the byte map of `T`'s fields, given by their names (0, 1, 2 for a tuple).
+macro_rules! fields {
+    ($t:ty, $($field:tt),+) => {
+        Row::with_fields(
+            stringify!($t),
+            size_of::<$t>(),
+            align_of::<$t>(),
+            &[$((offset_of!($t, $field), field_size(|value: &$t| &value.$field))),+],
+        )
+    };
+}
+
+/// The size of the field `get` reads, without needing to name its type.
+fn field_size<T, F>(_get: fn(&T) -> &F) -> usize {
+    size_of::<F>()
+}
+
+/// `Option<T>` next to `T`: the same size means `None` lives in a niche.
+macro_rules! option {
+    ($t:ty) => {{
+        let extra = size_of::<Option<$t>>() - size_of::<$t>();
+        let note = if extra == 0 {
+            String::from("niche: None uses a bit pattern T never has")
+        } else {
+            format!("no niche: {extra} more byte(s) than T for the tag")
+        };
+        // Built by hand: stringify!(Option<$t>) would space out the angle brackets.
+        let ty = format!("Option<{}>", stringify!($t));
+        Row::new(
+            &ty,
+            size_of::<Option<$t>>(),
+            align_of::<Option<$t>>(),
+            &note,
+        )
+    }};
+}
+
+/// The order `(i32, f64, u8)` is written in, kept by `#[repr(C)]` the way C would.
+#[repr(C)]
+#[allow(dead_code)]
+pub struct ReprC(i32, f64, u8);
+
+/// Every section of the report.
+pub fn report() -> Vec<Section> {
+    vec![
+        Section {
+            title: "Primitives",
+            rows: vec![
+                row!(bool, "only 0 and 1 are valid"),
+                row!(char, "a Unicode scalar value, up to 0x10FFFF"),
+                row!(i8, ""),
+                row!(i16, ""),
+                row!(i32, "the default integer"),
+                row!(i64, ""),
+                row!(i128, ""),
+                row!(isize, "pointer-sized"),
+                row!(u8, ""),
+                row!(u16, ""),
+                row!(u32, ""),
+                row!(u64, ""),
+                row!(u128, ""),
+                row!(usize, "pointer-sized: indexes and lengths"),
+                row!(f32, ""),
+                row!(f64, "the default float"),
+                row!((), "the unit type takes no memory"),
+            ],
+        },
+        Section {
+            title: "Tuples and structs",
+            rows: vec![
+                fields!((i32, f64, u8), 0, 1, 2),
+                {
+                    let mut row = fields!(ReprC, 0, 1, 2);
+                    row.note.push_str(", #[repr(C)] keeps the written order");
+                    row
+                },
+                fields!((u8, u16, u8), 0, 1, 2),
+                fields!((u8, u32), 0, 1),
+                fields!((char, u8), 0, 1),
+                fields!((u64, u8, u8), 0, 1, 2),
+            ],
+        },
+        Section {
+            title: "Arrays",
+            rows: vec![
+                row!([u8; 3], "no padding between elements of u8"),
+                row!([u16; 3], ""),
+                row!([i32; 4], "size is length × element size"),
+                row!([(u8, u32); 2], "each element keeps its own padding"),
+                row!([u64; 0], "empty, but still aligned"),
+            ],
+        },
+        Section {
+            title: "Option and niches",
+            rows: vec![
+                option!(bool),
+                option!(u8),
+                option!(u32),
+                option!(NonZeroU32),
+                option!(char),
+                option!(f64),
+                option!(&u8),
+                option!(Box<u8>),
+                option!(Vec<u8>),
+                option!(Option<bool>),
+            ],
+        },
+        Section {
+            title: "References and pointers",
+            rows: vec![
+                row!(&u8, "thin: an address"),
+                row!(&[u64; 4], "thin: the length is part of the type"),
+                row!(&[u8], "fat: address + length"),
+                row!(&str, "fat: address + length in bytes"),
+                row!(&dyn Display, "fat: address + vtable"),
+                row!(Box<u8>, "owns its heap value"),
+                row!(Box<[u8]>, "fat, like &[u8]"),
+                row!(*const u8, "raw pointer, no niche"),
+                row!(fn(), "function pointer"),
+            ],
+        },
+        Section {
+            title: "Vec and String headers",
+            rows: vec![
+                row!(
+                    Vec<u8>,
+                    "pointer, capacity and length; the elements are on the heap"
+                ),
+                row!(Vec<u64>, "the same header whatever the element"),
+                row!(String, "a Vec<u8> that holds UTF-8"),
+                row!(Box<str>, "no capacity: it can't grow"),
+                row!(Vec<Vec<u8>>, "only the outer header is here"),
+            ],
+        },
+    ]
+}
+
+const LEGEND: &str = "In the bytes column each byte shows the field that owns it (0 for .0, 1 for .1, ...) and . is padding.";
+
+/// The report as aligned columns for a terminal.
+pub fn terminal(sections: &[Section]) -> String {
+    let mut out = String::new();
+    for section in sections {
+        let type_width = section
+            .rows
+            .iter()
+            .map(|row| row.ty.len())
+            .max()
+            .unwrap_or(0);
+        let bytes_width = section
+            .rows
+            .iter()
+            .filter_map(|row| row.bytes.as_ref().map(String::len))
+            .max();
+
+        out.push_str(&format!(
+            "{}\n{}\n",
+            section.title,
+            "=".repeat(section.title.len())
+        ));
+        out.push_str(&format!(
+            "{:<type_width$}  {:>4}  {:>5}  ",
+            "type", "size", "align"
+        ));
+        if let Some(width) = bytes_width {
+            out.push_str(&format!("{:<width$}  ", "bytes"));
+        }
+        out.push_str("notes\n");
+
+        for row in &section.rows {
+            let mut line = format!(
+                "{:<type_width$}  {:>4}  {:>5}  ",
+                row.ty, row.size, row.align
+            );
+            if let Some(width) = bytes_width {
+                line.push_str(&format!("{:<width$}  ", row.bytes.as_deref().unwrap_or("")));
+            }
+            line.push_str(&row.note);
+            // No trailing spaces when the note is empty.
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out.push_str(LEGEND);
+    out.push('\n');
+    out
+}
+
+/// The report as Markdown tables, ready to paste into the docs.
+pub fn markdown(sections: &[Section]) -> String {
+    let mut out = String::new();
+    for section in sections {
+        let has_bytes = section.rows.iter().any(|row| row.bytes.is_some());
+
+        out.push_str(&format!("### {}\n\n", section.title));
+        if has_bytes {
+            out.push_str("| Type | Size | Align | Bytes | Notes |\n|---|---:|---:|---|---|\n");
+        } else {
+            out.push_str("| Type | Size | Align | Notes |\n|---|---:|---:|---|\n");
+        }
+
+        for row in &section.rows {
+            // Types go in backticks; a | would end the cell, so it is escaped.
+            let ty = row.ty.replace('|', "\\|");
+            out.push_str(&format!("| `{ty}` | {} | {} |", row.size, row.align));
+            if has_bytes {
+                out.push_str(&format!(" `{}` |", row.bytes.as_deref().unwrap_or("")));
+            }
+            out.push_str(&format!(" {} |\n", row.note));
+        }
+        out.push('\n');
+    }
+    out.push_str(LEGEND);
+    out.push('\n');
+    out
+}
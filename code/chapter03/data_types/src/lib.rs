@@ -0,0 +1,10 @@
+/*
+ * The library holds the interactive tools, src/main.rs runs them
+ * (or the tour of every data type when it gets no arguments).
+ * Tests in the tests/ folder use the library just like main.rs does.
+ */
+pub mod chars;
+pub mod float;
+pub mod layout;
+pub mod literal;
+pub mod overflow;
@@ -0,0 +1,546 @@
+/*
+ * The numeric literal parser and base converter.
+ *
+ * A literal is a value written straight into the code. Rust has a few forms:
+ * - integers in four bases: 255, 0xff, 0o377, 0b1111_1111 (underscores are ignored)
+ * - floats: 1.5, 1e-3, 2.5E10 (always decimal)
+ * - bytes and chars: b'A' is the u8 65, 'A' is the char U+0041
+ * - a suffix picks the type: 1_000u16, 1e-3f32. Without one the compiler infers it,
+ *   and if nothing decides it an integer is an i32 and a float an f64.
+ *
+ * A literal that doesn't fit its type, like 256u8, is rejected by the
+ * `overflowing_literals` lint, which is an error unless it is turned off;
+ * the messages here follow what rustc says.
+ * A leading `-` is not part of the literal: it negates it, so -128i8 is fine.
+ */
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::float::{self, FloatType};
+use crate::overflow::{self, IntType};
+
+/// A parsed literal and its value in every form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal {
+    /// The literal as it was typed.
+    pub text: String,
+    /// The resulting type, like "u16".
+    pub ty: String,
+    /// Why the literal has that type.
+    pub why: &'static str,
+    pub value: String,
+    /// The value written in other ways, like ("hexadecimal", "0xff").
+    pub forms: Vec<(&'static str, String)>,
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} is of type {} ({})", self.text, self.ty, self.why)?;
+        writeln!(f, "  {:<12}  {}", "value", self.value)?;
+        for (name, form) in &self.forms {
+            writeln!(f, "  {name:<12}  {form}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `digits` in groups of `size` from the right, like 1111_0000.
+fn group(digits: &str, size: usize) -> String {
+    let first = match digits.len() % size {
+        0 => size,
+        rest => rest,
+    };
+    let (head, tail) = digits.split_at(first.min(digits.len()));
+    let mut grouped = head.to_string();
+    for chunk in tail.as_bytes().chunks(size) {
+        grouped.push('_');
+        grouped.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+    }
+    grouped
+}
+
+/// The value in binary, octal, decimal and hexadecimal.
+///
+/// `bits` is what the type stores: for a negative number, its two's complement.
+fn bases(bits: u128, decimal: String, negative: bool) -> Vec<(&'static str, String)> {
+    let mut binary = format!("0b{}", group(&format!("{bits:b}"), 4));
+    let mut octal = format!("{bits:#o}");
+    let mut hexadecimal = format!("0x{}", group(&format!("{bits:x}"), 4));
+    // {:b}, {:o} and {:x} print a negative number's stored bits, never a minus sign.
+    if negative {
+        binary.push_str("  (two's complement, as {:b} prints it)");
+        octal.push_str("  (two's complement, as {:o} prints it)");
+        hexadecimal.push_str("  (two's complement, as {:x} prints it)");
+    }
+    vec![
+        ("binary", binary),
+        ("octal", octal),
+        ("decimal", decimal),
+        ("hexadecimal", hexadecimal),
+    ]
+}
+
+/// The smallest and biggest value of `ty`, as text.
+fn range(ty: IntType) -> String {
+    let shift = 128 - overflow::bits(ty);
+    if overflow::is_signed(ty) {
+        format!("{}..={}", i128::MIN >> shift, i128::MAX >> shift)
+    } else {
+        format!("0..={}", u128::MAX >> shift)
+    }
+}
+
+/// Whether `magnitude`, negated or not, is a value of `ty`.
+fn fits(ty: IntType, magnitude: u128, negative: bool) -> bool {
+    let shift = 128 - overflow::bits(ty);
+    match (overflow::is_signed(ty), negative) {
+        // i8 goes from -128 to 127: one more on the negative side.
+        (true, true) => magnitude <= (i128::MAX >> shift) as u128 + 1,
+        (true, false) => magnitude <= (i128::MAX >> shift) as u128,
+        (false, _) => magnitude <= u128::MAX >> shift,
+    }
+}
+
+/// The bits `magnitude` (negated or not) leaves in a `ty`.
+fn wrap(ty: IntType, magnitude: u128, negative: bool) -> u128 {
+    let value = if negative {
+        magnitude.wrapping_neg()
+    } else {
+        magnitude
+    };
+    value & (u128::MAX >> (128 - overflow::bits(ty)))
+}
+
+/// `bits` read as a value of `ty`: for signed types, a set top bit means negative.
+fn signed_value(ty: IntType, bits: u128) -> String {
+    let shift = 128 - overflow::bits(ty);
+    if overflow::is_signed(ty) {
+        // Shift the top bit into i128's sign bit and back, copying it along the way.
+        (((bits << shift) as i128) >> shift).to_string()
+    } else {
+        bits.to_string()
+    }
+}
+
+/// The type rustc suggests instead, by the same rules as its `overflowing_literals` lint:
+/// - a signed type suggests the unsigned type of the same size when the value is positive
+///   and fits there, in any base: 128i8 is usually meant as a u8. Otherwise it suggests
+///   the smallest bigger signed type that fits.
+/// - an unsigned type suggests the smallest unsigned type that fits, but only for
+///   hexadecimal, octal and binary literals: rustc gives a decimal one no help.
+/// - isize and usize get no suggestion, since their size depends on the target.
+fn suggestion(ty: IntType, magnitude: u128, negative: bool, decimal: bool) -> Option<IntType> {
+    let unsigned = [
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+    ];
+    let signed = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+    ];
+    if matches!(ty, IntType::Isize | IntType::Usize) {
+        return None;
+    }
+
+    if overflow::is_signed(ty) {
+        let same_size = unsigned
+            .into_iter()
+            .find(|other| overflow::bits(*other) == overflow::bits(ty));
+        if !negative
+            && let Some(other) = same_size
+            && fits(other, magnitude, false)
+        {
+            return Some(other);
+        }
+        signed.into_iter().find(|other| {
+            overflow::bits(*other) > overflow::bits(ty) && fits(*other, magnitude, negative)
+        })
+    } else if decimal {
+        None
+    } else {
+        unsigned
+            .into_iter()
+            .find(|other| fits(*other, magnitude, false))
+    }
+}
+
+/// A literal that looks like a number: its parts, before anything is checked.
+struct Number<'a> {
+    radix: u32,
+    /// The digits with the underscores still in.
+    digits: &'a str,
+    /// `.5`, `e-3` or both, for floats.
+    fraction: &'a str,
+    exponent: &'a str,
+    suffix: &'a str,
+}
+
+fn base_name(radix: u32) -> &'static str {
+    match radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hexadecimal",
+        _ => "decimal",
+    }
+}
+
+/// Splits a number literal the way the lexer does: prefix, digits, fraction, exponent, suffix.
+fn split(text: &str) -> Result<Number<'_>, String> {
+    let (radix, rest) = match text.get(..2) {
+        Some("0x") => (16, &text[2..]),
+        Some("0o") => (8, &text[2..]),
+        Some("0b") => (2, &text[2..]),
+        _ => (10, text),
+    };
+
+    /*
+     * The lexer takes every decimal digit even in binary and octal, and complains
+     * about the bad ones later. In hexadecimal a..f are digits, so in 0x1f32 the
+     * "f32" is part of the number, not a suffix.
+     */
+    let is_digit =
+        |c: char| c == '_' || c.is_ascii_digit() || (radix == 16 && c.is_ascii_hexdigit());
+    let end = rest.find(|c: char| !is_digit(c)).unwrap_or(rest.len());
+    let (digits, mut rest) = rest.split_at(end);
+
+    let mut fraction = "";
+    let mut exponent = "";
+    // `1.` is a float, but in `1.max(2)` the dot is a method call, so a letter can't follow.
+    if let Some(after) = rest.strip_prefix('.')
+        && !after.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '.')
+    {
+        if radix != 10 {
+            return Err(format!(
+                "{} float literal is not supported",
+                base_name(radix)
+            ));
+        }
+        let end = after
+            .find(|c: char| c != '_' && !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        fraction = &rest[..end + 1];
+        rest = &after[end..];
+    }
+    if radix == 10 && rest.starts_with(['e', 'E']) {
+        let signs = usize::from(rest[1..].starts_with(['+', '-']));
+        let after = &rest[1 + signs..];
+        let end = after
+            .find(|c: char| c != '_' && !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        if !after[..end].contains(|c: char| c.is_ascii_digit()) {
+            return Err(String::from("expected at least one digit in exponent"));
+        }
+        exponent = &rest[..1 + signs + end];
+        rest = &after[end..];
+    }
+
+    Ok(Number {
+        radix,
+        digits,
+        fraction,
+        exponent,
+        suffix: rest,
+    })
+}
+
+fn integer(
+    text: &str,
+    negative: bool,
+    number: &Number,
+    ty: Option<IntType>,
+) -> Result<Literal, String> {
+    let digits = number.digits.replace('_', "");
+    if digits.is_empty() {
+        return Err(String::from("no valid digits found for number"));
+    }
+    if let Some(bad) = digits.chars().find(|c| !c.is_digit(number.radix)) {
+        return Err(format!(
+            "invalid digit for a base {} literal: '{bad}'",
+            number.radix
+        ));
+    }
+    let magnitude = u128::from_str_radix(&digits, number.radix).map_err(|_| {
+        format!(
+            "integer literal is too large\n  = note: value exceeds limit of `{}`",
+            u128::MAX
+        )
+    })?;
+
+    let why = if ty.is_some() {
+        "from its suffix"
+    } else {
+        "no suffix, and with nothing else to decide, an integer literal is an i32"
+    };
+    let ty = ty.unwrap_or(IntType::I32);
+    let shown = if negative {
+        format!("-{text}")
+    } else {
+        text.to_string()
+    };
+
+    if negative && !overflow::is_signed(ty) {
+        return Err(format!(
+            "cannot apply unary operator `-` to type `{ty}`\n  = note: unsigned values cannot be negated"
+        ));
+    }
+    if !fits(ty, magnitude, negative) {
+        let decimal = number.radix == 10;
+        let mut message =
+            format!("literal out of range for `{ty}`\n  = note: the literal `{shown}` ");
+        if decimal {
+            message.push_str(&format!(
+                "does not fit into the type `{ty}` whose range is `{}`",
+                range(ty)
+            ));
+        } else {
+            // rustc explains what the bits would turn into, since that is often what was meant.
+            let wrapped = signed_value(ty, wrap(ty, magnitude, negative));
+            message.push_str(&format!(
+                "(decimal `{magnitude}`) does not fit into the type `{ty}` and will become `{wrapped}{ty}`"
+            ));
+        }
+        if let Some(other) = suggestion(ty, magnitude, negative, decimal) {
+            message.push_str(&format!(
+                "\n  = help: consider using the type `{other}` instead"
+            ));
+        }
+        return Err(message);
+    }
+
+    let bits = wrap(ty, magnitude, negative);
+    Ok(Literal {
+        text: shown,
+        ty: ty.to_string(),
+        why,
+        value: signed_value(ty, bits),
+        forms: bases(bits, signed_value(ty, bits), negative),
+    })
+}
+
+fn floating(
+    text: &str,
+    negative: bool,
+    number: &Number,
+    ty: Option<FloatType>,
+) -> Result<Literal, String> {
+    let why = if ty.is_some() {
+        "from its suffix"
+    } else {
+        "no suffix, and with nothing else to decide, a float literal is an f64"
+    };
+    let ty = ty.unwrap_or(FloatType::F64);
+    let shown = if negative {
+        format!("-{text}")
+    } else {
+        text.to_string()
+    };
+
+    let mut plain =
+        format!("{}{}{}", number.digits, number.fraction, number.exponent).replace('_', "");
+    if negative {
+        plain.insert(0, '-');
+    }
+    let report = float::inspect(&[&ty.to_string(), &plain])?;
+    if report.category == "infinity" {
+        return Err(format!(
+            "literal out of range for `{ty}`\n  = note: the literal `{shown}` does not fit into the type `{ty}` \
+             and will be converted to `{ty}::INFINITY`"
+        ));
+    }
+
+    let mut forms = vec![("bits", report.bits.clone())];
+    if let Some(exact) = report.exact.clone() {
+        forms.push(("exact value", exact));
+    }
+    forms.push(("inspect with", format!("cargo run -- float {ty} {plain}")));
+    Ok(Literal {
+        text: shown,
+        ty: ty.to_string(),
+        why,
+        value: report.shortest,
+        forms,
+    })
+}
+
+/// The code of the one char between the quotes of a char or byte literal.
+fn quoted(body: &str, byte: bool) -> Result<u32, String> {
+    let mut chars = body.chars();
+    let code = match (chars.next(), byte) {
+        (None, _) => return Err(String::from("empty character literal")),
+        (Some('\\'), _) => {
+            let escape: String = chars.by_ref().collect();
+            return match escape.as_str() {
+                "n" => Ok(0x0A),
+                "r" => Ok(0x0D),
+                "t" => Ok(0x09),
+                "\\" => Ok(0x5C),
+                "0" => Ok(0),
+                "'" => Ok(0x27),
+                "\"" => Ok(0x22),
+                _ if escape.starts_with('x') => {
+                    let code = u32::from_str_radix(&escape[1..], 16)
+                        .ok()
+                        .filter(|_| escape.len() == 3)
+                        .ok_or("invalid character in numeric character escape: use \\x and two hex digits")?;
+                    // In a char, \x only reaches ASCII; a byte can be anything.
+                    if !byte && code > 0x7F {
+                        return Err(String::from(
+                            "out of range hex escape\n  = help: a hex escape must be at most 7F, use \\u{...} for more",
+                        ));
+                    }
+                    Ok(code)
+                }
+                _ if escape.starts_with("u{") && escape.ends_with('}') => {
+                    if byte {
+                        return Err(String::from("unicode escape in byte string"));
+                    }
+                    let hex = &escape[2..escape.len() - 1];
+                    u32::from_str_radix(&hex.replace('_', ""), 16)
+                        .ok()
+                        .filter(|&code| char::from_u32(code).is_some())
+                        .ok_or_else(|| format!("invalid unicode character escape '\\u{{{hex}}}'"))
+                }
+                _ => Err(format!("unknown character escape: `{escape}`")),
+            };
+        }
+        (Some(c), true) if !c.is_ascii() => {
+            return Err(format!(
+                "non-ASCII character in byte literal\n  = help: use a \\xHH escape for a non-ASCII byte, like b'\\x{:X}'",
+                c.encode_utf8(&mut [0; 4]).as_bytes()[0]
+            ));
+        }
+        (Some(c), _) => u32::from(c),
+    };
+
+    if chars.next().is_some() {
+        return Err(String::from(
+            "character literal may only contain one codepoint",
+        ));
+    }
+    Ok(code)
+}
+
+fn character(text: &str, negative: bool, body: &str, byte: bool) -> Result<Literal, String> {
+    let ty = if byte { "u8" } else { "char" };
+    if negative {
+        return Err(format!("cannot apply unary operator `-` to type `{ty}`"));
+    }
+
+    let code = quoted(body, byte)?;
+    if byte {
+        let mut forms = bases(u128::from(code), code.to_string(), false);
+        forms.push(("as a char", format!("{:?}", char::from(code as u8))));
+        return Ok(Literal {
+            text: text.to_string(),
+            ty: String::from(ty),
+            why: "a byte literal is always a u8",
+            value: code.to_string(),
+            forms,
+        });
+    }
+
+    let c = char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER);
+    let utf8: Vec<String> = c
+        .encode_utf8(&mut [0; 4])
+        .bytes()
+        .map(|byte| format!("{byte:02X}"))
+        .collect();
+    let mut forms = vec![("code point", format!("U+{code:04X}"))];
+    forms.extend(bases(u128::from(code), code.to_string(), false));
+    forms.push(("UTF-8", utf8.join(" ")));
+    Ok(Literal {
+        text: text.to_string(),
+        ty: String::from(ty),
+        why: "a character literal is always a char",
+        value: format!("{c:?}"),
+        forms,
+    })
+}
+
+/// Parses one literal in Rust syntax, like `0xff`, `1_000u16`, `b'A'` or `1e-3f32`.
+pub fn parse(text: &str) -> Result<Literal, String> {
+    let text = text.trim();
+    let (negative, literal) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, text),
+    };
+
+    if let Some(body) = literal.strip_prefix("b'") {
+        let body = body
+            .strip_suffix('\'')
+            .ok_or("unterminated byte constant")?;
+        return character(literal, negative, body, true);
+    }
+    if let Some(body) = literal.strip_prefix('\'') {
+        let body = body
+            .strip_suffix('\'')
+            .ok_or("unterminated character literal")?;
+        return character(literal, negative, body, false);
+    }
+    if !literal.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!(
+            "'{literal}' is not a literal (try 0xff, 0o77, 0b1111_0000, 1_000u16, b'A' or 1e-3f32)"
+        ));
+    }
+
+    let number = split(literal)?;
+    let is_float = !number.fraction.is_empty() || !number.exponent.is_empty();
+    let suffix = number.suffix.trim_start_matches('_');
+
+    match suffix {
+        "" if is_float => floating(literal, negative, &number, None),
+        "" => integer(literal, negative, &number, None),
+        "f32" | "f64" => {
+            let ty = if suffix == "f32" {
+                FloatType::F32
+            } else {
+                FloatType::F64
+            };
+            if number.radix != 10 {
+                return Err(format!(
+                    "{} float literal is not supported",
+                    base_name(number.radix)
+                ));
+            }
+            floating(literal, negative, &number, Some(ty))
+        }
+        _ => match suffix.parse::<IntType>() {
+            Ok(ty) if !is_float => integer(literal, negative, &number, Some(ty)),
+            _ => {
+                let kind = if is_float { "float" } else { "number" };
+                Err(format!(
+                    "invalid suffix `{suffix}` for {kind} literal\n  = help: the suffix must be one of the numeric types (`u32`, `isize`, `f32`, etc.)"
+                ))
+            }
+        },
+    }
+}
+
+/// Parses literals line by line until an empty line or the end of input.
+pub fn run<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<()> {
+    writeln!(
+        output,
+        "Type a Rust literal, like `0xff`, `0b1111_0000`, `1_000u16`, `b'A'` or `1e-3f32`."
+    )?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(());
+        }
+
+        match parse(&line) {
+            Ok(literal) => writeln!(output, "\n{literal}")?,
+            Err(message) => writeln!(output, "error: {message}")?,
+        }
+    }
+}
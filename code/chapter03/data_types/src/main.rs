@@ -1,4 +1,84 @@
+use std::io;
+use std::process;
+
+use data_types::{chars, float, layout, literal, overflow};
+
+const USAGE: &str = "\
+Usage: data_types [TOOL [ARGS]]
+
+Without a tool, prints a tour of Rust's data types.
+
+Tools:
+  chars [TEXT]              each char of TEXT: code point, UTF-8, UTF-16, category and grapheme
+                            clusters, like `chars héllo`; \\u{301} types a code point;
+                            without arguments it asks for one line of text at a time
+  float [f32|f64] NUMBER    the bits of a float and the exact value they store, like `float 0.1`;
+  float [f32|f64] A OP B    the same for a result, like `float 0.1 + 0.2`;
+                            without arguments it asks for one number per line
+  layout [--markdown]       size, alignment and padding of primitive and compound types,
+                            as a table for the terminal or in Markdown for the docs
+  literal [LITERAL]         the type and value of a Rust literal in every base, like `literal 0xff`
+                            or `literal \"b'A'\"`; without arguments it asks for one per line
+  overflow [TYPE OP A [B]]  what each overflow strategy does, like `overflow u8 add 250 10`;
+                            without arguments it asks for one operation per line";
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match words[..] {
+        [] => tour(),
+        ["-h" | "--help"] => println!("{USAGE}"),
+        ["chars"] => run(chars::run(&mut io::stdin().lock(), &mut io::stdout())),
+        ["chars", ref rest @ ..] => match chars::unescape(&rest.join(" ")) {
+            Ok(text) => print!("{}", chars::inspect(&text)),
+            Err(message) => exit(&message),
+        },
+        ["float"] => run(float::run(&mut io::stdin().lock(), &mut io::stdout())),
+        ["float", ref rest @ ..] => match float::inspect(rest) {
+            Ok(report) => print!("{report}"),
+            Err(message) => exit(&message),
+        },
+        ["layout"] => print!("{}", layout::terminal(&layout::report())),
+        ["layout", "--markdown"] => print!("{}", layout::markdown(&layout::report())),
+        ["layout", other, ..] => exit_with_usage(&format!("layout doesn't take '{other}'")),
+        ["literal"] => run(literal::run(&mut io::stdin().lock(), &mut io::stdout())),
+        ["literal", ref rest @ ..] => match literal::parse(&rest.join(" ")) {
+            Ok(literal) => print!("{literal}"),
+            Err(message) => exit(&message),
+        },
+        ["overflow"] => run(overflow::run(&mut io::stdin().lock(), &mut io::stdout())),
+        ["overflow", ref rest @ ..] => match overflow::explore(rest) {
+            Ok(report) => print!("{report}"),
+            Err(message) => exit(&message),
+        },
+        [other, ..] => exit_with_usage(&format!("unknown tool '{other}'")),
+    }
+}
+
+fn run(result: io::Result<()>) {
+    if let Err(error) = result {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+}
+
+/// Stops on input the tool can't use. The arguments were fine, so the usage isn't repeated.
+fn exit(message: &str) -> ! {
+    eprintln!("error: {message}");
+    process::exit(1);
+}
+
+/// Stops on arguments that don't match any tool, and shows which ones do.
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("error: {message}\n\n{USAGE}");
+    process::exit(2);
+}
+
+/// Prints an example of every data type.
+// Literals like 3.14 are here to show float syntax, not to stand in for std::f64::consts::PI.
+#[allow(clippy::approx_constant)]
+fn tour() {
     println!("=== RUST DATA TYPES DEMONSTRATION ===\n");
 
     // ========================================
@@ -44,6 +124,8 @@ fn main() {
     println!("checked_add: {:?}", checked);
     println!("overflowing_add: ({}, {})", result, overflowed);
     println!("saturating_add: {}", saturated);
+    println!("(Try any type and operation with: cargo run -- overflow u8 add 250 10)");
+    println!("(See any literal in every base with: cargo run -- literal 0b1111_0000)");
 
     // ========================================
     // FLOATING-POINT TYPES
@@ -58,6 +140,7 @@ fn main() {
     println!("f32 example: {}", float_32);
     println!("f64 example: {}", float_64);
     println!("Default float type: {}", 3.14); // f64 by default
+    println!("(See the bits behind any float with: cargo run -- float 0.1 + 0.2)");
 
     // ========================================
     // BOOLEAN TYPE
@@ -92,6 +175,7 @@ fn main() {
     println!("Number as char: {}", number_char);
     println!("Unicode character: {}", unicode_char);
     println!("Character size: {} bytes", std::mem::size_of::<char>());
+    println!("(See the chars behind any text with: cargo run -- chars 'e\\u{{301}}👍🏽')");
 
     // ========================================
     // COMPOUND TYPES
@@ -188,6 +272,7 @@ fn main() {
         "Vector size: {} bytes",
         std::mem::size_of_val(&dynamic_vector)
     );
+    println!("(See the size, alignment and padding of many more types with: cargo run -- layout)");
 
     // ========================================
     // TYPE INFERENCE EXAMPLES
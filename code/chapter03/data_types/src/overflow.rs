@@ -0,0 +1,519 @@
+/*
+ * The integer overflow explorer.
+ *
+ * Every integer type has a fixed number of bits, so some results don't fit:
+ * 250_u8 + 10 would be 260, but a u8 stops at 255. Rust makes us choose what happens:
+ * - wrapping_*:    keep the low bits, so 260 becomes 4 (what the hardware does)
+ * - checked_*:     None when the result doesn't fit
+ * - overflowing_*: the wrapped result and a bool saying whether it overflowed
+ * - saturating_*:  stop at the type's MIN or MAX, so 260 becomes 255
+ * - the plain operator (`a + b`): panics in a debug build and wraps in a release build,
+ *   because `cargo build --release` turns overflow checks off.
+ *
+ * Try it with `cargo run -- overflow u8 add 250 10`, or `cargo run -- overflow` to be asked.
+ */
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// Every integer type Rust has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for IntType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|ty| ty.name() == name)
+            .ok_or_else(|| {
+                format!("unknown integer type '{s}' (expected i8..i128, u8..u128, isize or usize)")
+            })
+    }
+}
+
+/// The operations that can overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Pow,
+    Shl,
+    Neg,
+}
+
+impl Op {
+    pub const ALL: [Op; 6] = [Op::Add, Op::Sub, Op::Mul, Op::Pow, Op::Shl, Op::Neg];
+
+    /// The name used in method names, like the "add" in `wrapping_add`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Pow => "pow",
+            Op::Shl => "shl",
+            Op::Neg => "neg",
+        }
+    }
+
+    /// The plain Rust expression, like `a + b` or `a.pow(b)`.
+    fn expression(self, a: &str, b: &str) -> String {
+        match self {
+            Op::Add => format!("{a} + {b}"),
+            Op::Sub => format!("{a} - {b}"),
+            Op::Mul => format!("{a} * {b}"),
+            Op::Pow => format!("{a}.pow({b})"),
+            Op::Shl => format!("{a} << {b}"),
+            Op::Neg => format!("-{a}"),
+        }
+    }
+
+    /// The message a debug build panics with. `pow` is repeated multiplication.
+    fn panic_message(self) -> &'static str {
+        match self {
+            Op::Add => "attempt to add with overflow",
+            Op::Sub => "attempt to subtract with overflow",
+            Op::Mul | Op::Pow => "attempt to multiply with overflow",
+            Op::Shl => "attempt to shift left with overflow",
+            Op::Neg => "attempt to negate with overflow",
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Op {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "add" | "+" => Ok(Op::Add),
+            "sub" | "-" => Ok(Op::Sub),
+            "mul" | "*" => Ok(Op::Mul),
+            "pow" | "**" => Ok(Op::Pow),
+            "shl" | "<<" => Ok(Op::Shl),
+            "neg" => Ok(Op::Neg),
+            other => Err(format!(
+                "unknown operation '{other}' (expected add, sub, mul, pow, shl or neg)"
+            )),
+        }
+    }
+}
+
+/// The right-hand side of an operation: `pow` and `shl` always take a u32.
+#[derive(Debug, Clone, Copy)]
+enum Rhs<T> {
+    None,
+    Value(T),
+    Amount(u32),
+}
+
+/// What each overflow strategy gives for one operation.
+struct Strategies<T> {
+    wrapping: T,
+    checked: Option<T>,
+    overflowing: (T, bool),
+    /// `None` when the type has no saturating version of the operation.
+    saturating: Option<T>,
+}
+
+/*
+ * The twelve integer types have the same methods, but no trait in std lists them.
+ * This trait does, and the macro below writes its impl once per type.
+ */
+trait Integer: Copy + fmt::Display + fmt::Binary + FromStr {
+    const MIN: Self;
+    const MAX: Self;
+    const BITS: u32;
+    const SIGNED: bool;
+
+    fn strategies(self, op: Op, rhs: Rhs<Self>) -> Strategies<Self>;
+}
+
+macro_rules! integer {
+    (signed: $($t:ty),*) => {
+        $(integer!(@impl $t, true, |a: $t| Some(a.saturating_neg()));)*
+    };
+    // Unsigned types have no saturating_neg: every negative result would be 0.
+    (unsigned: $($t:ty),*) => {
+        $(integer!(@impl $t, false, |_: $t| None);)*
+    };
+    (@impl $t:ty, $signed:expr, $saturating_neg:expr) => {
+        impl Integer for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const BITS: u32 = <$t>::BITS;
+            const SIGNED: bool = $signed;
+
+            fn strategies(self, op: Op, rhs: Rhs<Self>) -> Strategies<Self> {
+                let a = self;
+                match (op, rhs) {
+                    (Op::Add, Rhs::Value(b)) => Strategies {
+                        wrapping: a.wrapping_add(b),
+                        checked: a.checked_add(b),
+                        overflowing: a.overflowing_add(b),
+                        saturating: Some(a.saturating_add(b)),
+                    },
+                    (Op::Sub, Rhs::Value(b)) => Strategies {
+                        wrapping: a.wrapping_sub(b),
+                        checked: a.checked_sub(b),
+                        overflowing: a.overflowing_sub(b),
+                        saturating: Some(a.saturating_sub(b)),
+                    },
+                    (Op::Mul, Rhs::Value(b)) => Strategies {
+                        wrapping: a.wrapping_mul(b),
+                        checked: a.checked_mul(b),
+                        overflowing: a.overflowing_mul(b),
+                        saturating: Some(a.saturating_mul(b)),
+                    },
+                    (Op::Pow, Rhs::Amount(exponent)) => Strategies {
+                        wrapping: a.wrapping_pow(exponent),
+                        checked: a.checked_pow(exponent),
+                        overflowing: a.overflowing_pow(exponent),
+                        saturating: Some(a.saturating_pow(exponent)),
+                    },
+                    // There is no saturating_shl: shifting never "goes past" MAX, bits just fall off.
+                    (Op::Shl, Rhs::Amount(shift)) => Strategies {
+                        wrapping: a.wrapping_shl(shift),
+                        checked: a.checked_shl(shift),
+                        overflowing: a.overflowing_shl(shift),
+                        saturating: None,
+                    },
+                    (Op::Neg, Rhs::None) => {
+                        let saturating_neg: fn($t) -> Option<$t> = $saturating_neg;
+                        Strategies {
+                            wrapping: a.wrapping_neg(),
+                            checked: a.checked_neg(),
+                            overflowing: a.overflowing_neg(),
+                            saturating: saturating_neg(a),
+                        }
+                    }
+                    _ => unreachable!("explore pairs every operation with its kind of operand"),
+                }
+            }
+        }
+    };
+}
+
+integer!(signed: i8, i16, i32, i64, i128, isize);
+integer!(unsigned: u8, u16, u32, u64, u128, usize);
+
+/// Runs `$body` with `$t` standing for the real Rust type `$ty` names.
+macro_rules! for_type {
+    ($ty:expr, $t:ident => $body:expr) => {
+        match $ty {
+            IntType::I8 => {
+                type $t = i8;
+                $body
+            }
+            IntType::I16 => {
+                type $t = i16;
+                $body
+            }
+            IntType::I32 => {
+                type $t = i32;
+                $body
+            }
+            IntType::I64 => {
+                type $t = i64;
+                $body
+            }
+            IntType::I128 => {
+                type $t = i128;
+                $body
+            }
+            IntType::Isize => {
+                type $t = isize;
+                $body
+            }
+            IntType::U8 => {
+                type $t = u8;
+                $body
+            }
+            IntType::U16 => {
+                type $t = u16;
+                $body
+            }
+            IntType::U32 => {
+                type $t = u32;
+                $body
+            }
+            IntType::U64 => {
+                type $t = u64;
+                $body
+            }
+            IntType::U128 => {
+                type $t = u128;
+                $body
+            }
+            IntType::Usize => {
+                type $t = usize;
+                $body
+            }
+        }
+    };
+}
+
+/// How many bits `ty` has. isize and usize have as many as a pointer.
+pub(crate) fn bits(ty: IntType) -> u32 {
+    for_type!(ty, T => <T as Integer>::BITS)
+}
+
+/// Whether `ty` can hold negative values.
+pub(crate) fn is_signed(ty: IntType) -> bool {
+    for_type!(ty, T => <T as Integer>::SIGNED)
+}
+
+/// Everything the explorer found out about one operation, ready to print.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ty: IntType,
+    pub op: Op,
+    /// The operation as Rust code, like `250_u8 + 10`.
+    pub expression: String,
+    /// The type's range, like `0..=255`.
+    pub range: String,
+    pub wrapping: String,
+    pub checked: String,
+    pub overflowing: String,
+    pub saturating: String,
+    /// What the plain operator does in a debug build.
+    pub debug: String,
+    /// What the plain operator does in a release build.
+    pub release: String,
+    pub overflowed: bool,
+    /// The operands and the wrapped result as the bits the machine stores.
+    pub bits: Vec<(String, String)>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}    ({} holds {})",
+            self.expression, self.ty, self.range
+        )?;
+        writeln!(f)?;
+
+        let op = self.op;
+        let rows = [
+            (format!("wrapping_{op}"), &self.wrapping),
+            (format!("checked_{op}"), &self.checked),
+            (format!("overflowing_{op}"), &self.overflowing),
+            (format!("saturating_{op}"), &self.saturating),
+            (String::from("debug build"), &self.debug),
+            (String::from("release build"), &self.release),
+        ];
+        for (strategy, result) in rows {
+            writeln!(f, "  {strategy:<16}  {result}")?;
+        }
+
+        writeln!(f)?;
+        let width = self
+            .bits
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        for (name, bits) in &self.bits {
+            writeln!(f, "  {name:>width$} = {bits}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads a number of type `T`, allowing `_` separators like Rust code does.
+fn parse_operand<T: Integer>(ty: IntType, text: &str) -> Result<T, String> {
+    text.replace('_', "").parse().map_err(|_| {
+        format!(
+            "'{text}' is not a {ty}, which holds {}..={}",
+            T::MIN,
+            T::MAX
+        )
+    })
+}
+
+fn parse_amount(op: Op, text: &str) -> Result<u32, String> {
+    text.replace('_', "")
+        .parse()
+        .map_err(|_| format!("{op} takes a u32 on the right, got '{text}'"))
+}
+
+fn report<T: Integer>(ty: IntType, op: Op, operands: &[&str]) -> Result<Report, String> {
+    let (a, rhs, b_text) = match (op, operands) {
+        (Op::Neg, [a]) => (parse_operand::<T>(ty, a)?, Rhs::None, None),
+        (Op::Pow | Op::Shl, [a, b]) => {
+            let amount = parse_amount(op, b)?;
+            (
+                parse_operand(ty, a)?,
+                Rhs::Amount(amount),
+                Some(amount.to_string()),
+            )
+        }
+        (Op::Add | Op::Sub | Op::Mul, [a, b]) => {
+            let b: T = parse_operand(ty, b)?;
+            (parse_operand(ty, a)?, Rhs::Value(b), Some(b.to_string()))
+        }
+        (Op::Neg, _) => return Err(String::from("neg takes one operand")),
+        _ => return Err(format!("{op} takes two operands")),
+    };
+
+    let strategies = a.strategies(op, rhs);
+    let (wrapped, overflowed) = strategies.overflowing;
+    // Without the brackets, -2_i8.pow(3) would mean -(2_i8.pow(3)).
+    let a_text = if a.to_string().starts_with('-') {
+        format!("({a}_{ty})")
+    } else {
+        format!("{a}_{ty}")
+    };
+    let expression = op.expression(&a_text, b_text.as_deref().unwrap_or(""));
+
+    // `-x` on an unsigned type is rejected before the program ever runs.
+    let unsigned_neg = op == Op::Neg && !T::SIGNED;
+    let (debug, release) = if unsigned_neg {
+        let error = format!("doesn't compile: cannot apply unary operator `-` to type `{ty}`");
+        (error.clone(), error)
+    } else if overflowed {
+        (
+            format!("panics: {}", op.panic_message()),
+            format!("{wrapped} (wraps: overflow checks are off)"),
+        )
+    } else {
+        (wrapped.to_string(), wrapped.to_string())
+    };
+
+    // {:0width$b} pads with zeros to the type's width; negative numbers show their two's complement.
+    let width = T::BITS as usize;
+    let mut bits = vec![(a.to_string(), format!("{a:0width$b}"))];
+    if let Rhs::Value(b) = rhs {
+        bits.push((b.to_string(), format!("{b:0width$b}")));
+    }
+    bits.push((
+        String::from("wrapped result"),
+        format!("{wrapped:0width$b}"),
+    ));
+
+    Ok(Report {
+        ty,
+        op,
+        expression,
+        range: format!("{}..={}", T::MIN, T::MAX),
+        wrapping: strategies.wrapping.to_string(),
+        checked: match strategies.checked {
+            Some(value) => format!("Some({value})"),
+            None => String::from("None"),
+        },
+        overflowing: format!("({wrapped}, {overflowed})"),
+        saturating: match strategies.saturating {
+            Some(value) => value.to_string(),
+            None => format!("(no such method for {ty})"),
+        },
+        debug,
+        release,
+        overflowed,
+        bits,
+    })
+}
+
+/// Explores `<type> <operation> <a> [b]`, like `u8 add 250 10`.
+pub fn explore(words: &[&str]) -> Result<Report, String> {
+    let [ty, op, operands @ ..] = words else {
+        return Err(String::from(
+            "expected a type, an operation and operands, like: u8 add 250 10",
+        ));
+    };
+    let ty: IntType = ty.parse()?;
+    let op: Op = op.parse()?;
+
+    // Pick the real Rust type for the name, then the same generic code runs for all of them.
+    for_type!(ty, T => report::<T>(ty, op, operands))
+}
+
+/// Asks for operations line by line until an empty line or the end of input.
+pub fn run<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<()> {
+    writeln!(
+        output,
+        "Type an operation as <type> <op> <a> [b], like `u8 add 250 10` or `i8 neg -128`."
+    )?;
+    writeln!(
+        output,
+        "Types: i8..i128, u8..u128, isize, usize. Operations: add, sub, mul, pow, shl, neg."
+    )?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match explore(&words) {
+            Ok(report) => writeln!(output, "\n{report}")?,
+            Err(message) => writeln!(output, "{message}")?,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::io::Cursor;
+
+use data_types::chars::{self, CharInfo};
+
+#[test]
+fn encodings_of_one_char() {
+    let info = CharInfo::new('€', 0);
+
+    assert_eq!(info.code_point(), "U+20AC");
+    assert_eq!(info.utf8, [0xE2, 0x82, 0xAC]);
+    assert_eq!(info.utf16, [0x20AC]);
+    assert_eq!(info.category, "Sc");
+    assert_eq!(info.category_name, "currency symbol");
+    assert!(!info.alphabetic && !info.numeric && !info.whitespace);
+
+    // Outside the Basic Multilingual Plane, UTF-16 needs a surrogate pair.
+    let info = CharInfo::new('𝄞', 0);
+    assert_eq!(info.code_point(), "U+1D11E");
+    assert_eq!(info.utf8.len(), 4);
+    assert_eq!(info.utf16, [0xD834, 0xDD1E]);
+}
+
+#[test]
+fn properties_follow_unicode() {
+    let letter = CharInfo::new('ß', 0);
+    assert_eq!(letter.category, "Ll");
+    assert!(letter.alphabetic);
+
+    let digit = CharInfo::new('٣', 0);
+    assert_eq!(digit.category, "Nd");
+    assert!(digit.numeric && !digit.alphabetic);
+
+    let space = CharInfo::new('\u{3000}', 0);
+    assert_eq!(space.category_name, "space separator");
+    assert!(space.whitespace);
+
+    assert_eq!(CharInfo::new('\n', 0).printable(), "\\n");
+    assert_eq!(CharInfo::new('\u{301}', 0).printable(), "\\u{301}");
+}
+
+#[test]
+fn combining_accents_and_emoji_are_one_cluster() {
+    let report = chars::inspect("ae\u{301}👍🏽");
+
+    assert_eq!(report.clusters.len(), 3);
+    assert_eq!(report.char_count(), 5);
+    assert_eq!(report.text.len(), 12);
+    assert_eq!(report.utf16_len(), 7);
+
+    let multi: Vec<&str> = report
+        .multi_char_clusters()
+        .map(|cluster| cluster.text.as_str())
+        .collect();
+    assert_eq!(multi, ["e\u{301}", "👍🏽"]);
+
+    let accent = &report.clusters[1].chars[1];
+    assert_eq!(accent.offset, 2);
+    assert_eq!(accent.category, "Mn");
+
+    let output = report.to_string();
+    assert!(output.contains("┌ \"e\\u{301}\" is one grapheme of 2 chars"));
+    assert!(output.contains("2 grapheme cluster(s) above are several chars"));
+}
+
+#[test]
+fn plain_text_has_no_highlights() {
+    let report = chars::inspect("héllo");
+
+    assert_eq!(report.clusters.len(), 5);
+    assert_eq!(report.multi_char_clusters().count(), 0);
+    assert!(!report.to_string().contains('┌'));
+}
+
+#[test]
+fn escapes_type_any_code_point() {
+    assert_eq!(chars::unescape("e\\u{301}!").unwrap(), "e\u{301}!");
+    assert_eq!(chars::unescape("no escapes").unwrap(), "no escapes");
+    assert!(
+        chars::unescape("\\u{D800}")
+            .unwrap_err()
+            .contains("not a Unicode scalar value")
+    );
+    assert!(
+        chars::unescape("\\u{41")
+            .unwrap_err()
+            .contains("missing its closing brace")
+    );
+}
+
+#[test]
+fn run_inspects_each_line() {
+    let mut input = Cursor::new("x\n\\u{zz}\n \n\ny\n");
+    let mut output = Vec::new();
+
+    chars::run(&mut input, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("\"x\" is 1 grapheme clusters"));
+    assert!(output.contains("'\\u{zz}' is not a Unicode scalar value"));
+    // A line with only a space is still text to inspect.
+    assert!(output.contains("\" \" is 1 grapheme clusters"));
+    // The empty line ends the session before y.
+    assert!(!output.contains("\"y\""));
+}
@@ -0,0 +1,141 @@
+use std::io::Cursor;
+
+use data_types::float::{self, FloatType};
+
+#[test]
+fn one_tenth_is_not_stored_exactly() {
+    let report = float::inspect(&["0.1"]).unwrap();
+
+    assert_eq!(report.ty, FloatType::F64);
+    assert_eq!(report.category, "normal");
+    assert_eq!(report.sign, 0);
+    assert_eq!(report.exponent, 1019);
+    assert_eq!(report.unbiased, Some(-4));
+    assert_eq!(report.mantissa, 0x9_9999_9999_999a);
+    assert_eq!(
+        report.bits,
+        "0 01111111011 1001100110011001100110011001100110011001100110011010"
+    );
+    assert_eq!(
+        report.exact.as_deref(),
+        Some("0.1000000000000000055511151231257827021181583404541015625")
+    );
+    assert_eq!(report.next_down, "0.09999999999999999");
+    assert_eq!(report.next_up, "0.10000000000000002");
+    assert_eq!(
+        report.ulp.as_deref(),
+        Some("2^-56 ≈ 1.3877787807814457e-17")
+    );
+}
+
+#[test]
+fn a_sum_shows_how_its_operands_were_rounded() {
+    let report = float::inspect(&["0.1", "+", "0.2"]).unwrap();
+
+    assert_eq!(report.shortest, "0.30000000000000004");
+    assert_eq!(
+        report.exact.as_deref(),
+        Some("0.3000000000000000444089209850062616169452667236328125")
+    );
+    assert_eq!(report.operands.len(), 2);
+    assert_eq!(
+        report.operands[1].1,
+        "0.200000000000000011102230246251565404236316680908203125"
+    );
+
+    // 0.3 on its own rounds to a different, smaller float.
+    let third = float::inspect(&["0.3"]).unwrap();
+    assert_eq!(
+        third.exact.as_deref(),
+        Some("0.299999999999999988897769753748434595763683319091796875")
+    );
+    assert!(
+        report
+            .to_string()
+            .contains("0.1 + 0.2 as f64 is 0.30000000000000004")
+    );
+}
+
+#[test]
+fn f32_has_fewer_bits() {
+    let report = float::inspect(&["f32", "16777217"]).unwrap();
+
+    assert_eq!(report.ty, FloatType::F32);
+    // 2^24 + 1 needs 25 bits of mantissa, so it rounds to 2^24.
+    assert_eq!(report.shortest, "16777216.0");
+    assert_eq!(report.exact.as_deref(), Some("16777216"));
+    assert_eq!(report.bits, "0 10010111 00000000000000000000000");
+    assert_eq!(report.next_up, "16777218.0");
+}
+
+#[test]
+fn special_values_are_classified() {
+    let subnormal = float::inspect(&["5e-324"]).unwrap();
+    assert_eq!(subnormal.category, "subnormal");
+    assert_eq!(subnormal.exponent, 0);
+    assert_eq!(subnormal.mantissa, 1);
+    assert_eq!(subnormal.next_down, "0.0");
+    assert_eq!(subnormal.ulp.as_deref(), Some("2^-1074 ≈ 5e-324"));
+
+    let zero = float::inspect(&["-0"]).unwrap();
+    assert_eq!(zero.category, "zero");
+    assert_eq!(zero.sign, 1);
+    assert_eq!(zero.formula, None);
+
+    let infinity = float::inspect(&["1", "/", "0"]).unwrap();
+    assert_eq!(infinity.category, "infinity");
+    assert_eq!(infinity.exact, None);
+    assert_eq!(infinity.unbiased, None);
+
+    let nan = float::inspect(&["f32", "0x7fc00000"]).unwrap();
+    assert_eq!(nan.category, "NaN");
+    assert_eq!(nan.shortest, "NaN");
+}
+
+#[test]
+fn large_numbers_are_written_out_in_full() {
+    let report = float::inspect(&["1e23"]).unwrap();
+
+    assert_eq!(report.exact.as_deref(), Some("99999999999999991611392"));
+    assert_eq!(
+        report.formula.as_deref(),
+        Some("+1.323488980084844168771951444796286523342132568359375 × 2^76")
+    );
+    assert_eq!(float::exact_decimal(1, -3), "0.125");
+    assert_eq!(float::exact_decimal(3, 40), "3298534883328");
+}
+
+#[test]
+fn bad_input_is_explained() {
+    assert!(
+        float::inspect(&["abc"])
+            .unwrap_err()
+            .contains("'abc' is not a number")
+    );
+    assert!(
+        float::inspect(&["1", "%", "2"])
+            .unwrap_err()
+            .contains("unknown operator '%'")
+    );
+    assert!(
+        float::inspect(&["f32", "0x1_0000_0000"])
+            .unwrap_err()
+            .contains("more than 32 bits")
+    );
+    assert!(float::inspect(&[]).is_err());
+}
+
+#[test]
+fn run_inspects_each_line() {
+    let mut input = Cursor::new("0.5\nnonsense\nf32 0.1 * 3\n\n0.25\n");
+    let mut output = Vec::new();
+
+    float::run(&mut input, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("0.5 as f64 is 0.5\n"));
+    assert!(output.contains("'nonsense' is not a number"));
+    assert!(output.contains("0.1 * 3 as f32 is 0.3\n"));
+    // The empty line ends the session before 0.25.
+    assert!(!output.contains("0.25"));
+}
@@ -0,0 +1,86 @@
+use std::mem::size_of;
+
+use data_types::layout::{self, Row, Section};
+
+fn find(sections: &[Section], ty: &str) -> Row {
+    sections
+        .iter()
+        .flat_map(|section| &section.rows)
+        .find(|row| row.ty == ty)
+        .unwrap_or_else(|| panic!("no row for {ty}"))
+        .clone()
+}
+
+#[test]
+fn primitives_have_their_size_and_alignment() {
+    let sections = layout::report();
+
+    let char_row = find(&sections, "char");
+    assert_eq!((char_row.size, char_row.align), (4, 4));
+    assert_eq!(find(&sections, "()").size, 0);
+    assert_eq!(find(&sections, "usize").size, size_of::<usize>());
+}
+
+#[test]
+fn byte_maps_show_every_field_and_the_padding() {
+    let row = Row::with_fields("(u8, u32)", 8, 4, &[(0, 1), (4, 4)]);
+    assert_eq!(row.bytes.as_deref(), Some("0...1111"));
+    assert_eq!(row.note, "3 padding byte(s)");
+
+    let row = Row::with_fields("(u32, u8)", 16, 8, &[(8, 4), (0, 1)]);
+    assert_eq!(row.bytes.as_deref(), Some("1....... 0000...."));
+    assert_eq!(row.note, "11 padding byte(s), fields reordered");
+}
+
+#[test]
+fn repr_c_keeps_the_written_order() {
+    let sections = layout::report();
+
+    let rust = find(&sections, "(i32, f64, u8)");
+    let c = find(&sections, "ReprC");
+    // Rust puts the f64 first to save padding; C keeps the order and pays for it.
+    assert!(rust.size < c.size);
+    assert!(rust.note.contains("fields reordered"));
+    assert!(c.bytes.unwrap().starts_with("0000"));
+}
+
+#[test]
+fn option_uses_niches_where_it_can() {
+    let sections = layout::report();
+
+    let reference = find(&sections, "Option<&u8>");
+    assert_eq!(reference.size, size_of::<&u8>());
+    assert!(reference.note.starts_with("niche"));
+
+    let byte = find(&sections, "Option<u8>");
+    assert_eq!(byte.size, 2);
+    assert!(byte.note.starts_with("no niche: 1 more byte(s)"));
+}
+
+#[test]
+fn headers_are_three_words() {
+    let sections = layout::report();
+    let word = size_of::<usize>();
+
+    assert_eq!(find(&sections, "Vec<u8>").size, 3 * word);
+    assert_eq!(find(&sections, "String").size, 3 * word);
+    assert_eq!(find(&sections, "&str").size, 2 * word);
+}
+
+#[test]
+fn both_outputs_list_every_row() {
+    let sections = layout::report();
+    let terminal = layout::terminal(&sections);
+    let markdown = layout::markdown(&sections);
+
+    for section in &sections {
+        assert!(terminal.contains(section.title));
+        assert!(markdown.contains(&format!("### {}", section.title)));
+        for row in &section.rows {
+            assert!(terminal.contains(&row.ty), "{} is missing", row.ty);
+            assert!(markdown.contains(&format!("| `{}` |", row.ty)));
+        }
+    }
+    assert!(markdown.contains("| Type | Size | Align | Bytes | Notes |"));
+    assert!(terminal.lines().all(|line| !line.ends_with(' ')));
+}
@@ -0,0 +1,198 @@
+use std::io::Cursor;
+
+use data_types::literal;
+
+fn form<'a>(literal: &'a literal::Literal, name: &str) -> &'a str {
+    literal
+        .forms
+        .iter()
+        .find(|(form, _)| *form == name)
+        .map(|(_, text)| text.as_str())
+        .unwrap_or_else(|| panic!("no {name} form"))
+}
+
+#[test]
+fn integers_in_every_base() {
+    let hex = literal::parse("0xff").unwrap();
+    assert_eq!(hex.ty, "i32");
+    assert_eq!(hex.value, "255");
+    assert_eq!(form(&hex, "binary"), "0b1111_1111");
+    assert_eq!(form(&hex, "octal"), "0o377");
+
+    let binary = literal::parse("0b1111_0000").unwrap();
+    assert_eq!(binary.value, "240");
+    assert_eq!(form(&binary, "hexadecimal"), "0xf0");
+
+    let octal = literal::parse("0o77").unwrap();
+    assert_eq!(octal.value, "63");
+
+    let suffixed = literal::parse("1_000u16").unwrap();
+    assert_eq!(suffixed.ty, "u16");
+    assert_eq!(suffixed.why, "from its suffix");
+    assert_eq!(form(&suffixed, "hexadecimal"), "0x3e8");
+}
+
+#[test]
+fn negative_literals_show_their_twos_complement() {
+    let literal = literal::parse("-128i8").unwrap();
+
+    assert_eq!(literal.value, "-128");
+    assert!(form(&literal, "binary").starts_with("0b1000_0000"));
+    assert_eq!(
+        form(&literal, "octal"),
+        "0o200  (two's complement, as {:o} prints it)"
+    );
+    assert_eq!(
+        form(&literal, "hexadecimal"),
+        "0x80  (two's complement, as {:x} prints it)"
+    );
+}
+
+#[test]
+fn hex_digits_are_not_a_float_suffix() {
+    let literal = literal::parse("0x1f32").unwrap();
+
+    assert_eq!(literal.ty, "i32");
+    assert_eq!(literal.value, "7986");
+}
+
+#[test]
+fn bytes_and_chars() {
+    let byte = literal::parse("b'A'").unwrap();
+    assert_eq!(byte.ty, "u8");
+    assert_eq!(byte.value, "65");
+
+    let escaped = literal::parse(r"b'\x7f'").unwrap();
+    assert_eq!(escaped.value, "127");
+
+    let crab = literal::parse(r"'\u{1F980}'").unwrap();
+    assert_eq!(crab.ty, "char");
+    assert_eq!(crab.value, "'🦀'");
+    assert_eq!(form(&crab, "UTF-8"), "F0 9F A6 80");
+
+    assert!(
+        literal::parse("b'é'")
+            .unwrap_err()
+            .starts_with("non-ASCII character in byte literal")
+    );
+    assert_eq!(
+        literal::parse("'ab'").unwrap_err(),
+        "character literal may only contain one codepoint"
+    );
+}
+
+#[test]
+fn floats_are_decimal_only() {
+    let literal = literal::parse("1e-3f32").unwrap();
+    assert_eq!(literal.ty, "f32");
+    assert_eq!(literal.value, "0.001");
+    assert_eq!(
+        form(&literal, "exact value"),
+        "0.001000000047497451305389404296875"
+    );
+
+    assert_eq!(literal::parse("2.5").unwrap().ty, "f64");
+    assert_eq!(
+        literal::parse("0x1.8").unwrap_err(),
+        "hexadecimal float literal is not supported"
+    );
+    assert_eq!(
+        literal::parse("0b1f32").unwrap_err(),
+        "binary float literal is not supported"
+    );
+    assert!(
+        literal::parse("1.5u8")
+            .unwrap_err()
+            .starts_with("invalid suffix `u8` for float literal")
+    );
+}
+
+#[test]
+fn decimal_overflow_reports_the_range() {
+    assert_eq!(
+        literal::parse("256u8").unwrap_err(),
+        "literal out of range for `u8`\n  \
+         = note: the literal `256u8` does not fit into the type `u8` whose range is `0..=255`"
+    );
+    assert_eq!(
+        literal::parse("128i8").unwrap_err(),
+        "literal out of range for `i8`\n  \
+         = note: the literal `128i8` does not fit into the type `i8` whose range is `-128..=127`\n  \
+         = help: consider using the type `u8` instead"
+    );
+    // Without a suffix the literal is an i32.
+    assert_eq!(
+        literal::parse("3_000_000_000").unwrap_err(),
+        "literal out of range for `i32`\n  \
+         = note: the literal `3_000_000_000` does not fit into the type `i32` whose range is \
+         `-2147483648..=2147483647`\n  \
+         = help: consider using the type `u32` instead"
+    );
+    assert!(
+        literal::parse("-129i8")
+            .unwrap_err()
+            .contains("consider using the type `i16`")
+    );
+}
+
+#[test]
+fn other_bases_report_what_the_bits_become() {
+    assert_eq!(
+        literal::parse("0xff_i8").unwrap_err(),
+        "literal out of range for `i8`\n  \
+         = note: the literal `0xff_i8` (decimal `255`) does not fit into the type `i8` and will become `-1i8`\n  \
+         = help: consider using the type `u8` instead"
+    );
+    assert!(
+        literal::parse("0x1ff_u8")
+            .unwrap_err()
+            .contains("will become `255u8`")
+    );
+}
+
+#[test]
+fn other_errors_follow_rustc() {
+    assert!(
+        literal::parse("-1u8")
+            .unwrap_err()
+            .starts_with("cannot apply unary operator `-` to type `u8`")
+    );
+    assert!(
+        literal::parse("1e40f32")
+            .unwrap_err()
+            .contains("will be converted to `f32::INFINITY`")
+    );
+    assert!(
+        literal::parse("1000000000000000000000000000000000000000")
+            .unwrap_err()
+            .starts_with("integer literal is too large")
+    );
+    assert_eq!(
+        literal::parse("0b102").unwrap_err(),
+        "invalid digit for a base 2 literal: '2'"
+    );
+    assert_eq!(
+        literal::parse("0x").unwrap_err(),
+        "no valid digits found for number"
+    );
+    assert!(
+        literal::parse("1u7")
+            .unwrap_err()
+            .starts_with("invalid suffix `u7` for number literal")
+    );
+    assert!(literal::parse("abc").is_err());
+}
+
+#[test]
+fn run_parses_each_line() {
+    let mut input = Cursor::new("0xff\n256u8\n\n7\n");
+    let mut output = Vec::new();
+
+    literal::run(&mut input, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("0xff is of type i32"));
+    assert!(output.contains("error: literal out of range for `u8`"));
+    // The empty line ends the session before 7.
+    assert!(!output.contains("7 is of type"));
+}
@@ -0,0 +1,100 @@
+use data_types::overflow::{self, IntType, Op};
+
+#[test]
+fn every_strategy_for_a_u8_addition() {
+    let report = overflow::explore(&["u8", "add", "250", "10"]).unwrap();
+
+    assert_eq!(report.expression, "250_u8 + 10");
+    assert_eq!(report.range, "0..=255");
+    assert_eq!(report.wrapping, "4");
+    assert_eq!(report.checked, "None");
+    assert_eq!(report.overflowing, "(4, true)");
+    assert_eq!(report.saturating, "255");
+    assert_eq!(report.debug, "panics: attempt to add with overflow");
+    assert!(report.release.starts_with("4 "));
+    assert!(report.overflowed);
+}
+
+#[test]
+fn results_that_fit_are_the_same_everywhere() {
+    let report = overflow::explore(&["i64", "mul", "-3", "7"]).unwrap();
+
+    assert_eq!(report.expression, "(-3_i64) * 7");
+    assert_eq!(report.checked, "Some(-21)");
+    assert_eq!(report.debug, "-21");
+    assert_eq!(report.release, "-21");
+    assert!(!report.overflowed);
+}
+
+#[test]
+fn signed_minimum_cannot_be_negated() {
+    let report = overflow::explore(&["i8", "neg", "-128"]).unwrap();
+
+    assert_eq!(report.wrapping, "-128");
+    assert_eq!(report.saturating, "127");
+    assert_eq!(report.debug, "panics: attempt to negate with overflow");
+    assert_eq!(report.bits.last().unwrap().1, "10000000");
+}
+
+#[test]
+fn unsigned_negation_does_not_compile() {
+    let report = overflow::explore(&["u32", "neg", "1"]).unwrap();
+
+    assert_eq!(report.wrapping, "4294967295");
+    assert!(report.saturating.contains("no such method"));
+    assert!(report.debug.starts_with("doesn't compile"));
+}
+
+#[test]
+fn pow_and_shl_take_a_u32() {
+    let report = overflow::explore(&["u128", "pow", "2", "128"]).unwrap();
+    assert_eq!(report.wrapping, "0");
+    assert_eq!(report.saturating, u128::MAX.to_string());
+    assert_eq!(report.debug, "panics: attempt to multiply with overflow");
+
+    let report = overflow::explore(&["usize", "shl", "1", "3"]).unwrap();
+    assert_eq!(report.wrapping, "8");
+    assert!(!report.overflowed);
+
+    assert!(overflow::explore(&["u8", "shl", "1", "-1"]).is_err());
+}
+
+#[test]
+fn bad_input_is_explained() {
+    assert!(
+        overflow::explore(&["u8", "add", "300", "1"])
+            .unwrap_err()
+            .contains("0..=255")
+    );
+    assert!(overflow::explore(&["u7", "add", "1", "1"]).is_err());
+    assert!(overflow::explore(&["u8", "div", "1", "1"]).is_err());
+    assert!(overflow::explore(&["u8", "add", "1"]).is_err());
+    assert!(overflow::explore(&["u8"]).is_err());
+}
+
+#[test]
+fn names_parse_back() {
+    for ty in IntType::ALL {
+        assert_eq!(ty.name().parse(), Ok(ty));
+    }
+    for op in Op::ALL {
+        assert_eq!(op.name().parse(), Ok(op));
+    }
+    assert_eq!("<<".parse(), Ok(Op::Shl));
+}
+
+#[test]
+fn the_loop_answers_each_line() {
+    let mut output = Vec::new();
+    overflow::run(
+        &mut "u8 sub 0 1\nnonsense add 1 1\n\nu8 add 1 1\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("0_u8 - 1"));
+    assert!(output.contains("unknown integer type 'nonsense'"));
+    // The empty line ends the loop.
+    assert!(!output.contains("1_u8 + 1"));
+}